@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::Parser;
+use oxc_jsx_dom_expressions::{transform_with_options, Config, TransformOptions};
+use rayon::prelude::*;
+
+/// Compile the JSX of the files with dom-expressions
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// the files, directories or glob patterns to compile
+    #[arg(required_unless_present = "schema")]
+    inputs: Vec<String>,
+    /// the JSON or TOML file with the config of the transform
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// the directory the compiled files are written to
+    #[arg(short, long, required_unless_present_any = ["check", "schema"])]
+    out_dir: Option<PathBuf>,
+    /// only report the diagnostics, without writing any file
+    #[arg(long)]
+    check: bool,
+    /// print the JSON schema of the config file
+    #[arg(long, exclusive = true)]
+    schema: bool,
+}
+
+/// a file to compile, along with the directory its output path is relative to
+struct Input {
+    path: PathBuf,
+    base: PathBuf,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    if args.schema {
+        let schema = serde_json::to_string_pretty(&Config::json_schema());
+        println!("{}", schema.expect("the schema is serializable"));
+        return ExitCode::SUCCESS;
+    }
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// compile the inputs, returning whether all of them compiled without errors
+fn run(args: &Args) -> Result<bool, String> {
+    let config = match &args.config {
+        Some(path) => read_config(path)?,
+        None => Config::default(),
+    };
+    let inputs = collect_inputs(&args.inputs)?;
+
+    let failures = inputs
+        .par_iter()
+        .filter(|input| {
+            if let Err(error) = compile(input, &config, args) {
+                eprintln!("{}:\n{}", input.path.display(), error);
+                return true;
+            }
+            false
+        })
+        .count();
+    if failures > 0 {
+        eprintln!("{} of {} files failed to compile", failures, inputs.len());
+    }
+    Ok(failures == 0)
+}
+
+fn read_config(path: &Path) -> Result<Config, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read {}: {}", path.display(), error))?;
+    let config = match path
+        .extension()
+        .is_some_and(|extension| extension == "toml")
+    {
+        true => toml::from_str(&content).map_err(|error| error.to_string()),
+        false => serde_json::from_str(&content).map_err(|error| error.to_string()),
+    };
+    config.map_err(|error| format!("invalid config {}: {}", path.display(), error))
+}
+
+fn compile(input: &Input, config: &Config, args: &Args) -> Result<(), String> {
+    let source = fs::read_to_string(&input.path).map_err(|error| error.to_string())?;
+    let out_path = args
+        .out_dir
+        .as_ref()
+        .filter(|_| !args.check)
+        .map(|out_dir| {
+            let relative = input.path.strip_prefix(&input.base).unwrap_or(&input.path);
+            out_dir
+                .join(relative)
+                .with_extension(output_extension(&input.path))
+        });
+    if let Some(parent) = out_path.as_ref().and_then(|out_path| out_path.parent()) {
+        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    // the source map points to the input relative to the directory the map is written to
+    let filename = match out_path.as_ref().and_then(|out_path| out_path.parent()) {
+        Some(out_dir) => relative_path(&input.path, out_dir)?,
+        None => input.path.to_string_lossy().into_owned(),
+    };
+    let output = transform_with_options(
+        source,
+        config.clone(),
+        TransformOptions {
+            filename: Some(filename),
+            source_maps: out_path.is_some(),
+            ..Default::default()
+        },
+    )
+    .map_err(|error| error.to_string())?;
+    let Some(out_path) = out_path else {
+        return Ok(());
+    };
+
+    let mut code = output.code;
+    if let Some(map) = output.map {
+        let mut map_path = out_path.clone().into_os_string();
+        map_path.push(".map");
+        let map_path = PathBuf::from(map_path);
+        fs::write(&map_path, map.to_json_string()).map_err(|error| error.to_string())?;
+        if let Some(name) = map_path.file_name() {
+            code.push_str(&format!(
+                "//# sourceMappingURL={}\n",
+                name.to_string_lossy()
+            ));
+        }
+    }
+    fs::write(&out_path, code).map_err(|error| error.to_string())
+}
+
+/// the JSX is compiled away but the types are kept, so TypeScript files stay TypeScript files
+fn output_extension(path: &Path) -> &'static str {
+    match path.extension().is_some_and(|extension| extension == "tsx") {
+        true => "ts",
+        false => "js",
+    }
+}
+
+/// the path of the file relative to the directory, joined with slashes like source maps expect
+fn relative_path(path: &Path, dir: &Path) -> Result<String, String> {
+    let canonicalize = |path: &Path| {
+        fs::canonicalize(path)
+            .map_err(|error| format!("failed to resolve {}: {}", path.display(), error))
+    };
+    let path = canonicalize(path)?;
+    let dir = canonicalize(dir)?;
+    let common = path
+        .components()
+        .zip(dir.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parents = dir.components().skip(common).map(|_| "..".to_string());
+    let rest = path
+        .components()
+        .skip(common)
+        .map(|component| component.as_os_str().to_string_lossy().into_owned());
+    Ok(parents.chain(rest).collect::<Vec<_>>().join("/"))
+}
+
+/// find the JSX files of the inputs, walking directories and expanding glob patterns
+fn collect_inputs(patterns: &[String]) -> Result<Vec<Input>, String> {
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        let path = Path::new(pattern);
+        if path.is_dir() {
+            walk_dir(path, path, &mut inputs)?;
+            continue;
+        }
+        let paths = glob::glob(pattern)
+            .map_err(|error| format!("invalid pattern {}: {}", pattern, error))?;
+        let base = glob_base(path);
+        for path in paths {
+            let path = path.map_err(|error| error.to_string())?;
+            if path.is_file() && is_jsx_file(&path) {
+                inputs.push(Input {
+                    path,
+                    base: base.clone(),
+                });
+            }
+        }
+    }
+    if inputs.is_empty() {
+        return Err("no JSX files matched the inputs".to_string());
+    }
+    Ok(inputs)
+}
+
+fn walk_dir(dir: &Path, base: &Path, inputs: &mut Vec<Input>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|error| format!("failed to read {}: {}", dir.display(), error))?;
+    for entry in entries {
+        let path = entry.map_err(|error| error.to_string())?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name == "node_modules") {
+                continue;
+            }
+            walk_dir(&path, base, inputs)?;
+        } else if is_jsx_file(&path) {
+            inputs.push(Input {
+                path,
+                base: base.to_path_buf(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// the directory a glob pattern starts matching from, which is the part before its first
+/// wildcard, or the directory of the file for a plain path
+fn glob_base(pattern: &Path) -> PathBuf {
+    let is_wildcard = |component: &str| component.contains(['*', '?', '[', '{']);
+    let base = pattern
+        .components()
+        .take_while(|component| !is_wildcard(&component.as_os_str().to_string_lossy()))
+        .collect::<PathBuf>();
+    match base == pattern {
+        true => base.parent().map(Path::to_path_buf).unwrap_or_default(),
+        false => base,
+    }
+}
+
+fn is_jsx_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "jsx" || extension == "tsx")
+}
@@ -0,0 +1,109 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// create an empty directory for the files of the test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "jsx-dom-expressions-cli-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write(path: &Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_jsx-dom-expressions"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+const COMPONENT: &str = "export const Comp = () => <div>Hello, world!</div>;";
+
+#[test]
+fn compiles_directories_into_the_out_dir() {
+    let dir = temp_dir("directories");
+    write(&dir.join("src/app.jsx"), COMPONENT);
+    write(&dir.join("src/components/button.tsx"), COMPONENT);
+    write(&dir.join("src/styles.css"), "div {}");
+    write(&dir.join("src/node_modules/dep/index.jsx"), COMPONENT);
+
+    let output = run(&dir, &["src", "--out-dir", "dist"]);
+    assert!(output.status.success(), "{:?}", output);
+
+    let dist = dir.join("dist");
+    assert!(dist.join("app.js").is_file());
+    assert!(dist.join("app.js.map").is_file());
+    // the types of TypeScript files are kept
+    assert!(dist.join("components/button.ts").is_file());
+    assert!(!dist.join("styles.js").exists());
+    assert!(!dist.join("node_modules").exists());
+
+    let code = fs::read_to_string(dist.join("app.js")).unwrap();
+    assert!(code.ends_with("//# sourceMappingURL=app.js.map\n"));
+    let map: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(dist.join("components/button.ts.map")).unwrap())
+            .unwrap();
+    assert_eq!(
+        map["sources"],
+        serde_json::json!(["../../src/components/button.tsx"])
+    );
+}
+
+#[test]
+fn compiles_glob_patterns() {
+    let dir = temp_dir("glob");
+    write(&dir.join("src/a.jsx"), COMPONENT);
+    write(&dir.join("src/nested/b.jsx"), COMPONENT);
+
+    let output = run(&dir, &["src/**/*.jsx", "--out-dir", "dist"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(dir.join("dist/a.js").is_file());
+    assert!(dir.join("dist/nested/b.js").is_file());
+}
+
+#[test]
+fn checks_without_writing() {
+    let dir = temp_dir("check");
+    write(&dir.join("src/app.jsx"), COMPONENT);
+
+    let output = run(&dir, &["src", "--check"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+}
+
+#[test]
+fn fails_on_invalid_files() {
+    let dir = temp_dir("invalid");
+    write(&dir.join("src/app.jsx"), COMPONENT);
+    write(&dir.join("src/broken.jsx"), "const Comp = () => <div>;");
+
+    let output = run(&dir, &["src", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("broken.jsx"), "{}", stderr);
+    assert!(
+        stderr.contains("1 of 2 files failed to compile"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn fails_without_inputs() {
+    let dir = temp_dir("empty");
+    let output = run(&dir, &[".", "--check"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no JSX files"));
+}
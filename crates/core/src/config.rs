@@ -1,3 +1,10 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer};
+
+/// the options of the transform, deserialized from the same camelCase keys as the options of the
+/// babel plugin
+#[derive(Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
 pub struct Config {
     pub module_name: String,
     pub generate: OutputType,
@@ -10,13 +17,39 @@ pub struct Config {
     pub omit_nested_closing_tags: bool,
     pub context_to_custom_elements: bool,
     pub static_marker: String,
-    pub effect_wrapper: String,
-    pub memo_wrapper: String,
+    /// the helper running the updates of dynamic attributes, without it they are only set once
+    #[serde(deserialize_with = "effect_wrapper")]
+    #[schemars(with = "Wrapper")]
+    pub effect_wrapper: Option<String>,
+    /// the helper memoizing conditions and dynamic expressions, without it they are passed
+    /// through unwrapped
+    #[serde(deserialize_with = "memo_wrapper")]
+    #[schemars(with = "Wrapper")]
+    pub memo_wrapper: Option<String>,
     pub validate: bool,
+    /// the renderers owning a set of elements in dynamic mode, the other elements are created by
+    /// the universal renderer of `module_name`
+    pub renderers: Vec<RendererConfig>,
 }
 
+/// a renderer that creates some of the elements in dynamic mode
+#[derive(Clone, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RendererConfig {
+    /// the backend generating the code for the elements, `dom` or `universal`
+    pub name: String,
+    pub module_name: String,
+    pub elements: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputType {
     Dom,
+    Ssr,
+    Universal,
+    /// pick the renderer of each element from `Config::renderers`
+    Dynamic,
 }
 
 impl Default for Config {
@@ -33,9 +66,60 @@ impl Default for Config {
             omit_nested_closing_tags: false,
             context_to_custom_elements: false,
             static_marker: "@once".to_string(),
-            effect_wrapper: "effect".to_string(),
-            memo_wrapper: "memo".to_string(),
+            effect_wrapper: Some("effect".to_string()),
+            memo_wrapper: Some("memo".to_string()),
             validate: true,
+            renderers: Vec::new(),
         }
     }
 }
+
+impl Config {
+    /// get the JSON schema of the options
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(Config)
+    }
+
+    /// get the module the helpers of the renderer are imported from
+    pub fn renderer_module(&self, name: &str) -> &str {
+        self.renderers
+            .iter()
+            .find(|renderer| renderer.name == name)
+            .map_or(&self.module_name, |renderer| &renderer.module_name)
+    }
+
+    /// get the name of the renderer that creates the element
+    pub fn element_renderer(&self, tag_name: &str) -> Option<&str> {
+        self.renderers
+            .iter()
+            .find(|renderer| renderer.elements.iter().any(|element| element == tag_name))
+            .map(|renderer| renderer.name.as_str())
+    }
+}
+
+/// the name of a wrapper, `false` to disable it or `true` to keep the default one
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum Wrapper {
+    Name(String),
+    Enabled(bool),
+}
+
+fn wrapper<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    default: &str,
+) -> Result<Option<String>, D::Error> {
+    Ok(match Wrapper::deserialize(deserializer)? {
+        Wrapper::Name(name) => Some(name),
+        Wrapper::Enabled(true) => Some(default.to_string()),
+        Wrapper::Enabled(false) => None,
+    })
+}
+
+fn effect_wrapper<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    wrapper(deserializer, "effect")
+}
+
+fn memo_wrapper<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    wrapper(deserializer, "memo")
+}
@@ -1,82 +1,1450 @@
-use oxc::ast::ast;
+use html_escape::decode_html_entities;
+use oxc::{
+    allocator::CloneIn,
+    ast::{ast, NONE},
+    semantic::SymbolFlags,
+    span::{Atom, GetSpan, GetSpanMut, Span, SPAN},
+    syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+};
 use oxc_traverse::TraverseCtx;
 
-use crate::shared::transform::{JsxTransform, TransformInfo, TransformResult};
+use crate::{
+    shared::{
+        constants::{
+            attribute_alias, is_property, property_alias, svg_namespace, ALWAYS_CLOSE,
+            BLOCK_ELEMENTS, BOOLEANS, CHILD_PROPERTIES, DELEGATED_EVENTS, INLINE_ELEMENTS,
+            RESERVED_NAMESPACES, SVG_ELEMENTS, VOID_ELEMENTS,
+        },
+        diagnostics,
+        transform::{DynamicAttr, JsxTransform, TransformInfo, TransformResult},
+        utils::{
+            arrow_function, assign_identifier, assign_member, assignment_target, call_expression,
+            check_length, escape_html, formal_parameter, get_tag_name, is_component,
+            is_const_binding, is_meaningful_child, object_method, object_property,
+            static_expression_value, static_member, static_truthiness, strip_ts_wrappers,
+            take_server_only, template_literal, trim_whitespace, var_declarator, DynamicCheck,
+        },
+    },
+    OutputType,
+};
 
-impl<'a> JsxTransform {
+/// an attribute of a native element, with string values turned into string literals
+struct Attribute<'a> {
+    key: String,
+    value: Option<ast::Expression<'a>>,
+    /// whether the value was written as an expression container
+    container: bool,
+    /// the attribute in the source, which the code setting it maps back to
+    span: Span,
+}
+
+/// how an attribute gets set on the element
+pub struct SetAttrOptions<'a, 's> {
+    pub is_svg: bool,
+    pub is_ce: bool,
+    /// whether the update runs inside of an effect
+    pub dynamic: bool,
+    /// the previous value, for helpers that diff against it
+    pub prev_id: Option<ast::Expression<'a>>,
+    pub tag_name: &'s str,
+}
+
+impl<'a> JsxTransform<'a> {
     pub fn transform_element_dom(
-        &self,
-        el: &ast::JSXElement<'a>,
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
         ctx: &mut TraverseCtx<'a>,
+        info: &TransformInfo,
     ) -> TransformResult<'a> {
-        let tag_name = match &el.opening_element.name {
-            ast::JSXElementName::Identifier(ident) => ident.name.clone(),
-            _ => {
-                // TODO
+        let tag_name = get_tag_name(el);
+        let void_tag = VOID_ELEMENTS.contains(&tag_name.as_str());
+        // svg elements outside of an `<svg>` need to be created in the svg namespace
+        let wrap_svg =
+            info.top_level && tag_name != "svg" && SVG_ELEMENTS.contains(&tag_name.as_str());
+
+        let mut results = TransformResult {
+            template: Some(match wrap_svg {
+                true => format!("<svg><{}", tag_name),
+                false => format!("<{}", tag_name),
+            }),
+            tag_name: Some(tag_name.clone()),
+            is_svg: wrap_svg,
+            renderer: Some(OutputType::Dom),
+            ..TransformResult::new(ctx)
+        };
+        // the root element only needs a reference when something in it has to be updated
+        if !info.skip_id && (!info.top_level || self.has_expressions_dom(el)) {
+            results.id = Some(
+                ctx.generate_uid_in_current_scope("el$", SymbolFlags::FunctionScopedVariable)
+                    .name,
+            );
+        }
+
+        // the document and server only elements are already in the page when hydrating, so they
+        // are claimed instead of cloned from a template. Without hydration there is no server
+        // markup to claim, so a server only element is rendered like any other once its flag is
+        // dropped
+        let server_only = take_server_only(el);
+        if self.config.hydratable {
+            results.skip_template =
+                server_only || matches!(tag_name.as_str(), "html" | "head" | "body");
+            // the head isn't hydrated, its content is left to the server
+            if tag_name == "head" && info.top_level {
                 return TransformResult {
-                    id: None,
-                    template: None,
-                    exprs: ctx.ast.vec(),
-                    text: false,
-                    skip_template: false,
+                    exprs: ctx.ast.vec1(self.no_hydration_dom(ctx)),
+                    tag_name: Some(tag_name),
+                    renderer: Some(OutputType::Dom),
+                    ..TransformResult::new(ctx)
                 };
             }
-        };
+        }
 
-        let attributes = self.generate_attributes_dom(&el.opening_element.attributes);
-        let child_templates = self.generate_child_templates_dom(&el.children, ctx);
+        let is_ce = is_custom_element(&tag_name, el);
+        let text_placeholder = self.transform_attributes_dom(el, &mut results, ctx);
+        if let Some(template) = &mut results.template {
+            template.push('>');
+        }
+        if self.config.context_to_custom_elements && (tag_name == "slot" || is_ce) {
+            if let Some(id) = results.id.clone() {
+                let get_owner = self.register_renderer_method("getOwner", "dom", ctx);
+                let owner = call_expression(get_owner, [], ctx);
+                results.exprs.push(assign_member(
+                    ctx.ast.expression_identifier_reference(SPAN, id),
+                    "_$owner",
+                    owner,
+                    ctx,
+                ));
+            }
+        }
 
-        // TODO
-        let template = format!("<{}{}>{}", tag_name, attributes, child_templates);
+        if !void_tag {
+            // the closing tag of the last element can be left out, unless one of its ancestors
+            // has to be closed explicitly
+            let to_be_closed = !info.last_element
+                || info.to_be_closed.as_ref().is_some_and(|to_be_closed| {
+                    !self.config.omit_nested_closing_tags || to_be_closed.contains(&tag_name)
+                });
+            results.to_be_closed = match to_be_closed {
+                true => {
+                    let mut set = info.to_be_closed.clone().unwrap_or_else(|| {
+                        ALWAYS_CLOSE.iter().map(|tag| tag.to_string()).collect()
+                    });
+                    set.insert(tag_name.clone());
+                    if INLINE_ELEMENTS.contains(&tag_name.as_str()) {
+                        set.extend(BLOCK_ELEMENTS.iter().map(|tag| tag.to_string()));
+                    }
+                    Some(set)
+                }
+                false => info.to_be_closed.clone(),
+            };
+            if text_placeholder {
+                // the text node updated by a dynamic `textContent`
+                if let Some(template) = &mut results.template {
+                    template.push(' ');
+                }
+            } else if tag_name != "noscript" {
+                self.transform_children_dom(el, &mut results, ctx);
+            }
+            if to_be_closed {
+                if let Some(template) = &mut results.template {
+                    template.push_str(&format!("</{}>", tag_name));
+                }
+            }
+        }
+        if wrap_svg {
+            if let Some(template) = &mut results.template {
+                template.push_str("</svg>");
+            }
+        }
 
-        TransformResult {
-            id: None,
-            template: Some(template),
-            exprs: ctx.ast.vec(),
-            text: false,
-            skip_template: false,
+        if info.top_level {
+            let dynamics = std::mem::take(&mut results.dynamics);
+            if let Some(effect) = self.wrap_dynamics_dom(dynamics, ctx) {
+                results.exprs.push(effect);
+            }
+            let post_exprs = std::mem::replace(&mut results.post_exprs, ctx.ast.vec());
+            results.exprs.extend(post_exprs);
+            if self.config.hydratable && results.has_hydratable_event {
+                let run_hydration_events =
+                    self.register_renderer_method("runHydrationEvents", "dom", ctx);
+                let call = call_expression(run_hydration_events, [], ctx);
+                results.exprs.push(call);
+            }
         }
+
+        results
     }
 
-    /// generate attributes string without quotes around values
-    fn generate_attributes_dom(&self, attrs: &[ast::JSXAttributeItem<'a>]) -> String {
-        let mut attrs_string = String::new();
-        for attr_item in attrs {
-            let ast::JSXAttributeItem::Attribute(attr) = attr_item else {
-                continue;
+    /// Add the static attributes to the template and generate the code setting the others,
+    /// returning whether the element needs a text node for a dynamic `textContent`
+    fn transform_attributes_dom(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> bool {
+        let tag_name = results.tag_name.clone().unwrap_or_default();
+        let elem = results.id.clone();
+        let is_svg = SVG_ELEMENTS.contains(&tag_name.as_str());
+        let is_ce = is_custom_element(&tag_name, el);
+        let has_children = !el.children.is_empty();
+
+        let mut attributes = Vec::new();
+        // from the first spread on, attributes are merged into the props passed to `spread`
+        let mut spread_args = Vec::new();
+        let mut running_object = ctx.ast.vec();
+        let mut dynamic_spread = false;
+        let items = std::mem::replace(&mut el.opening_element.attributes, ctx.ast.vec());
+        for item in items {
+            let mut attr = match item {
+                ast::JSXAttributeItem::Attribute(attr) => attr,
+                ast::JSXAttributeItem::SpreadAttribute(mut spread) => {
+                    if !running_object.is_empty() {
+                        spread_args.push(ctx.ast.expression_object(
+                            SPAN,
+                            std::mem::replace(&mut running_object, ctx.ast.vec()),
+                            None,
+                        ));
+                    }
+                    let argument = ctx.ast.move_expression(&mut spread.argument);
+                    let (argument, dynamic) = self.transform_spread_argument(argument, ctx);
+                    dynamic_spread |= dynamic;
+                    spread_args.push(argument);
+                    continue;
+                }
+            };
+            let key = match &attr.name {
+                ast::JSXAttributeName::Identifier(ident) => ident.name.to_string(),
+                ast::JSXAttributeName::NamespacedName(name) => {
+                    format!("{}:{}", name.namespace.name, name.property.name)
+                }
             };
-            let ast::JSXAttributeName::Identifier(ident) = &attr.name else {
+            // refs, directives, properties and events are still handled on their own
+            if !spread_args.is_empty()
+                && !(key == "ref"
+                    || key.starts_with("use:")
+                    || key.starts_with("prop:")
+                    || key.starts_with("on"))
+            {
+                if !(has_children && key == "children") {
+                    if let Some(prop) = self.spread_property(&key, attr.value.take(), ctx) {
+                        running_object.push(prop);
+                    }
+                }
                 continue;
+            }
+            let (value, container) = match attr.value.take() {
+                None => (None, false),
+                Some(ast::JSXAttributeValue::StringLiteral(lit)) => (
+                    Some(
+                        ctx.ast
+                            .expression_string_literal(lit.span, decode_html_entities(&lit.value)),
+                    ),
+                    false,
+                ),
+                Some(ast::JSXAttributeValue::ExpressionContainer(mut container)) => {
+                    // `children` on a native element is the same as passing them as children
+                    if key == "children" && !has_children {
+                        el.children
+                            .push(ast::JSXChild::ExpressionContainer(container));
+                        continue;
+                    }
+                    let value = container
+                        .expression
+                        .as_expression_mut()
+                        .map(|expr| ctx.ast.move_expression(expr));
+                    (value, true)
+                }
+                Some(ast::JSXAttributeValue::Element(element)) => {
+                    (Some(ast::Expression::JSXElement(element)), true)
+                }
+                Some(ast::JSXAttributeValue::Fragment(frag)) => {
+                    (Some(ast::Expression::JSXFragment(frag)), true)
+                }
+            };
+            attributes.push(Attribute {
+                key,
+                value,
+                container,
+                span: attr.span,
+            });
+        }
+
+        results.is_import_node = is_ce
+            || (matches!(tag_name.as_str(), "img" | "iframe")
+                && attributes.iter().any(|attr| {
+                    attr.key == "loading"
+                        && matches!(&attr.value, Some(ast::Expression::StringLiteral(lit)) if lit.value == "lazy")
+                }));
+
+        let mut spread_call = None;
+        if !spread_args.is_empty() {
+            if !running_object.is_empty() {
+                spread_args.push(ctx.ast.expression_object(SPAN, running_object, None));
+            }
+            let props = match spread_args.len() > 1 || dynamic_spread {
+                true => {
+                    let merge_props = self.register_import_method("mergeProps", ctx);
+                    call_expression(merge_props, spread_args, ctx)
+                }
+                false => spread_args.remove(0),
             };
-            let name = ident.name.as_ref();
+            if let Some(elem) = elem.clone() {
+                let spread = self.register_renderer_method("spread", "dom", ctx);
+                let call = call_expression(
+                    spread,
+                    [
+                        ctx.ast.expression_identifier_reference(SPAN, elem),
+                        props,
+                        ctx.ast.expression_boolean_literal(SPAN, is_svg),
+                        ctx.ast.expression_boolean_literal(SPAN, has_children),
+                    ],
+                    ctx,
+                );
+                spread_call = Some(call);
+                // the spread props may contain events, which can't be known at compile time
+                results.has_hydratable_event = true;
+            }
+        }
+
+        let attributes = expand_attribute_objects(attributes, ctx);
+        let attributes = merge_class_attributes(attributes, ctx);
+
+        let mut needs_spacing = true;
+        let mut text_placeholder = false;
+        for Attribute {
+            key,
+            mut value,
+            mut container,
+            span,
+        } in attributes
+        {
+            let namespace = key
+                .split_once(':')
+                .map(|(namespace, _)| namespace)
+                .filter(|namespace| RESERVED_NAMESPACES.contains(namespace));
+
+            // values that can be evaluated at compile time are treated like string literals
+            if container && !key.starts_with("use:") {
+                if let Some(expr) = &value {
+                    if let Some(evaluated) = static_expression_value(expr) {
+                        let is_number = matches!(expr, ast::Expression::NumericLiteral(_));
+                        if !is_number || !(is_property(&key) || key.starts_with("prop:")) {
+                            value = Some(ctx.ast.expression_string_literal(SPAN, evaluated));
+                            container = false;
+                        }
+                    }
+                }
+            }
+
+            // boolean attributes with a known value end up in the template
+            if namespace == Some("bool") {
+                let truthy = match &value {
+                    Some(expr) => static_truthiness(expr),
+                    None => Some(true),
+                };
+                if let Some(truthy) = truthy {
+                    if truthy {
+                        let name = key.trim_start_matches("bool:");
+                        push_attribute_name(results, name, needs_spacing);
+                        needs_spacing = true;
+                    }
+                    continue;
+                }
+            }
+
+            let is_expression = namespace.is_some()
+                || (container
+                    && !matches!(
+                        value,
+                        Some(
+                            ast::Expression::StringLiteral(_) | ast::Expression::NumericLiteral(_)
+                        )
+                    ));
+            if is_expression {
+                let Some(elem) = elem.clone() else {
+                    continue;
+                };
+                let value = value.unwrap_or_else(|| ctx.ast.expression_boolean_literal(SPAN, true));
+                let options = SetAttrOptions {
+                    is_svg,
+                    is_ce,
+                    dynamic: false,
+                    prev_id: None,
+                    tag_name: &tag_name,
+                };
 
-            match &attr.value {
-                Some(ast::JSXAttributeValue::StringLiteral(str_lit)) => {
-                    let value = str_lit.value.as_ref();
-                    attrs_string.push_str(&format!(" {}={}", name, value));
+                if key == "ref" {
+                    self.transform_ref("dom", elem, value, results, ctx);
+                    // the ref is handed to the element first
+                    if let Some(call) = results.exprs.first_mut() {
+                        *call.span_mut() = span;
+                    }
+                } else if let Some(name) = key.strip_prefix("use:") {
+                    let use_fn = self.register_renderer_method("use", "dom", ctx);
+                    let directive = ctx
+                        .ast
+                        .expression_identifier_reference(SPAN, ctx.ast.atom(name));
+                    let accessor = arrow_function(ctx.ast.vec(), value, ctx);
+                    let elem = ctx.ast.expression_identifier_reference(SPAN, elem);
+                    let mut call = call_expression(use_fn, [directive, elem, accessor], ctx);
+                    *call.span_mut() = span;
+                    results.exprs.insert(0, call);
+                } else if key == "children" {
+                    // the children of the element take precedence
+                } else if key.starts_with("on") {
+                    self.transform_event_dom(elem, &key, value, span, results, ctx);
+                } else if self.config.effect_wrapper.is_some()
+                    && (key == "style"
+                        || key == "classList"
+                        || self.is_dynamic(
+                            &value,
+                            DynamicCheck {
+                                check_member: true,
+                                ..Default::default()
+                            },
+                            ctx,
+                        ))
+                {
+                    // the value of form controls is set after their children, as a `<select>`
+                    // needs its options to exist before its value can be set
+                    if let (true, Some(effect_wrapper)) = (
+                        key == "value" || key == "checked",
+                        self.config.effect_wrapper.clone(),
+                    ) {
+                        let mut set = self.set_attr_dom(&elem, &key, value, options, ctx);
+                        *set.span_mut() = span;
+                        let effect = self.register_import_method(&effect_wrapper, ctx);
+                        let arrow_fn = arrow_function(ctx.ast.vec(), set, ctx);
+                        let mut call = call_expression(effect, [arrow_fn], ctx);
+                        *call.span_mut() = span;
+                        results.post_exprs.push(call);
+                        continue;
+                    }
+                    let mut dynamic_elem = elem;
+                    if key == "textContent" {
+                        let text_id = ctx
+                            .generate_uid_in_current_scope(
+                                "el$",
+                                SymbolFlags::FunctionScopedVariable,
+                            )
+                            .name;
+                        let first_child = static_member(
+                            ctx.ast.expression_identifier_reference(SPAN, dynamic_elem),
+                            "firstChild",
+                            ctx,
+                        );
+                        results.declarations.push(var_declarator(
+                            text_id.clone(),
+                            first_child,
+                            ctx,
+                        ));
+                        dynamic_elem = text_id;
+                        text_placeholder = !has_children;
+                    }
+                    results.dynamics.push(DynamicAttr {
+                        elem: dynamic_elem,
+                        key,
+                        value,
+                        is_svg,
+                        is_ce,
+                        tag_name: tag_name.clone(),
+                        span,
+                    });
+                } else {
+                    let mut set = self.set_attr_dom(&elem, &key, value, options, ctx);
+                    *set.span_mut() = span;
+                    results.exprs.push(set);
                 }
-                Some(_) => {
-                    // TODO
+                continue;
+            }
+
+            let key = attribute_alias(&key).map(String::from).unwrap_or(key);
+            let value = match value {
+                Some(value) if CHILD_PROPERTIES.contains(&key.as_str()) => {
+                    let Some(elem) = elem.clone() else {
+                        continue;
+                    };
+                    let options = SetAttrOptions {
+                        is_svg,
+                        is_ce,
+                        dynamic: false,
+                        prev_id: None,
+                        tag_name: &tag_name,
+                    };
+                    let mut set = self.set_attr_dom(&elem, &key, value, options, ctx);
+                    *set.span_mut() = span;
+                    results.exprs.push(set);
+                    continue;
                 }
-                None => {
-                    // attributes without a value (e.g., <input disabled />)
-                    attrs_string.push_str(&format!(" {}", name));
+                value => value,
+            };
+
+            let key = match is_svg {
+                true => key,
+                false => key.to_lowercase(),
+            };
+            push_attribute_name(results, &key, needs_spacing);
+            let Some(value) = value else {
+                needs_spacing = true;
+                continue;
+            };
+            let mut text = static_expression_value(&value).unwrap_or_default();
+            if key == "style" || key == "class" {
+                text = trim_whitespace(&text);
+                if key == "style" {
+                    text = text.replace("; ", ";").replace(": ", ":");
                 }
             }
+            let attribute_value = match text.is_empty() {
+                true => "=\"\"".to_string(),
+                false if needs_quotes(&text) => format!("=\"{}\"", escape_html(&text, true)),
+                false => format!("={}", escape_html(&text, true)),
+            };
+            needs_spacing = !attribute_value.ends_with('"');
+            if let Some(template) = &mut results.template {
+                template.push_str(&attribute_value);
+            }
         }
-        attrs_string
+        if let Some(call) = spread_call {
+            results.exprs.push(call);
+        }
+        text_placeholder
     }
 
-    /// Process children and collect their templates
-    fn generate_child_templates_dom(
+    /// create the property of an attribute that follows a spread, which is passed to `spread`
+    /// along with the spread props
+    pub(crate) fn spread_property(
         &self,
-        children: &[ast::JSXChild<'a>],
+        key: &str,
+        value: Option<ast::JSXAttributeValue<'a>>,
         ctx: &mut TraverseCtx<'a>,
-    ) -> String {
-        let info = TransformInfo::default();
-        children
+    ) -> Option<ast::ObjectPropertyKind<'a>> {
+        let value = match value {
+            None => match BOOLEANS.contains(&key) {
+                true => ctx.ast.expression_boolean_literal(SPAN, true),
+                false => ctx.ast.expression_string_literal(SPAN, ""),
+            },
+            Some(ast::JSXAttributeValue::StringLiteral(lit)) => ctx
+                .ast
+                .expression_string_literal(lit.span, decode_html_entities(&lit.value)),
+            Some(ast::JSXAttributeValue::ExpressionContainer(mut container)) => ctx
+                .ast
+                .move_expression(container.expression.as_expression_mut()?),
+            Some(ast::JSXAttributeValue::Element(element)) => ast::Expression::JSXElement(element),
+            Some(ast::JSXAttributeValue::Fragment(frag)) => ast::Expression::JSXFragment(frag),
+        };
+        let check = DynamicCheck {
+            check_member: true,
+            check_tags: true,
+            ..Default::default()
+        };
+        Some(match self.is_dynamic(&value, check, ctx) {
+            true => {
+                let statements = ctx.ast.vec1(ctx.ast.statement_return(SPAN, Some(value)));
+                object_method(ast::PropertyKind::Get, key, ctx.ast.vec(), statements, ctx)
+            }
+            false => object_property(key, value, ctx),
+        })
+    }
+
+    /// create the code that sets the attribute on the element
+    pub fn set_attr_dom(
+        &mut self,
+        elem: &Atom<'a>,
+        name: &str,
+        value: ast::Expression<'a>,
+        options: SetAttrOptions<'a, '_>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let elem_ref =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, elem.clone());
+        let (namespace, name) = match name.split_once(':') {
+            Some((namespace, name)) if RESERVED_NAMESPACES.contains(&namespace) => {
+                (Some(namespace), name)
+            }
+            _ => (None, name),
+        };
+        let name_literal =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_string_literal(SPAN, name.to_string());
+
+        match namespace {
+            Some("style") => {
+                let style_method = |method: &str, ctx: &TraverseCtx<'a>| {
+                    static_member(static_member(elem_ref(ctx), "style", ctx), method, ctx)
+                };
+                let remove = |ctx: &TraverseCtx<'a>| {
+                    call_expression(
+                        style_method("removeProperty", ctx),
+                        [name_literal(ctx)],
+                        ctx,
+                    )
+                };
+                return match value {
+                    ast::Expression::StringLiteral(_) => call_expression(
+                        style_method("setProperty", ctx),
+                        [name_literal(ctx), value],
+                        ctx,
+                    ),
+                    ast::Expression::NullLiteral(_) => remove(ctx),
+                    ast::Expression::Identifier(ref ident) if ident.name == "undefined" => {
+                        remove(ctx)
+                    }
+                    value => {
+                        let new_value = match options.prev_id {
+                            Some(prev_id) => prev_id,
+                            None => value.clone_in(ctx.ast.allocator),
+                        };
+                        let test = ctx.ast.expression_binary(
+                            SPAN,
+                            value,
+                            BinaryOperator::Inequality,
+                            ctx.ast.expression_null_literal(SPAN),
+                        );
+                        let set = call_expression(
+                            style_method("setProperty", ctx),
+                            [name_literal(ctx), new_value],
+                            ctx,
+                        );
+                        ctx.ast.expression_conditional(SPAN, test, set, remove(ctx))
+                    }
+                };
+            }
+            Some("class") => {
+                let toggle = static_member(
+                    static_member(elem_ref(ctx), "classList", ctx),
+                    "toggle",
+                    ctx,
+                );
+                let value = match options.dynamic {
+                    true => value,
+                    false => {
+                        let not = ctx
+                            .ast
+                            .expression_unary(SPAN, UnaryOperator::LogicalNot, value);
+                        ctx.ast
+                            .expression_unary(SPAN, UnaryOperator::LogicalNot, not)
+                    }
+                };
+                return call_expression(toggle, [name_literal(ctx), value], ctx);
+            }
+            _ => {}
+        }
+
+        if name == "style" || name == "classList" {
+            let helper = self.register_renderer_method(name, "dom", ctx);
+            let mut args = vec![elem_ref(ctx), value];
+            args.extend(options.prev_id);
+            return call_expression(helper, args, ctx);
+        }
+        if !options.is_svg && name == "class" {
+            let helper = self.register_renderer_method("className", "dom", ctx);
+            return call_expression(helper, [elem_ref(ctx), value], ctx);
+        }
+        if options.dynamic && name == "textContent" {
+            if self.config.hydratable {
+                let helper = self.register_renderer_method("setProperty", "dom", ctx);
+                let data = ctx.ast.expression_string_literal(SPAN, "data");
+                return call_expression(helper, [elem_ref(ctx), data, value], ctx);
+            }
+            return assign_member(elem_ref(ctx), "data", value, ctx);
+        }
+        if namespace == Some("bool") {
+            let helper = self.register_renderer_method("setBoolAttribute", "dom", ctx);
+            return call_expression(helper, [elem_ref(ctx), name_literal(ctx), value], ctx);
+        }
+
+        let is_child_prop = CHILD_PROPERTIES.contains(&name);
+        let is_prop = is_property(name);
+        if namespace != Some("attr")
+            && (is_child_prop
+                || (!options.is_svg && is_prop)
+                || options.is_ce
+                || namespace == Some("prop"))
+        {
+            let name =
+                match options.is_ce && !is_child_prop && !is_prop && namespace != Some("prop") {
+                    true => to_property_name(name),
+                    false => name.to_string(),
+                };
+            if self.config.hydratable && namespace != Some("prop") {
+                let helper = self.register_renderer_method("setProperty", "dom", ctx);
+                let name = ctx.ast.expression_string_literal(SPAN, name);
+                return call_expression(helper, [elem_ref(ctx), name, value], ctx);
+            }
+            let property = property_alias(&name, &options.tag_name.to_uppercase())
+                .map(String::from)
+                .unwrap_or(name);
+            return assign_member(elem_ref(ctx), &property, value, ctx);
+        }
+
+        let is_namespaced = name.contains(':');
+        let name = attribute_alias(name).unwrap_or(name);
+        let name = match options.is_svg {
+            true => name.to_string(),
+            false => name.to_lowercase(),
+        };
+        let svg_ns = name
+            .split_once(':')
+            .filter(|_| is_namespaced)
+            .and_then(|(prefix, _)| svg_namespace(prefix));
+        let name_literal = ctx.ast.expression_string_literal(SPAN, name.clone());
+        match svg_ns {
+            Some(ns) => {
+                let helper = self.register_renderer_method("setAttributeNS", "dom", ctx);
+                let ns = ctx.ast.expression_string_literal(SPAN, ns);
+                call_expression(helper, [elem_ref(ctx), ns, name_literal, value], ctx)
+            }
+            None => {
+                let helper = self.register_renderer_method("setAttribute", "dom", ctx);
+                call_expression(helper, [elem_ref(ctx), name_literal, value], ctx)
+            }
+        }
+    }
+
+    /// Attach an event handler, either delegated to the document or as a listener on the element
+    fn transform_event_dom(
+        &mut self,
+        elem: Atom<'a>,
+        key: &str,
+        handler: ast::Expression<'a>,
+        span: Span,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let elem_ref =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, elem.clone());
+        if let Some(event) = key.strip_prefix("on:") {
+            let add_event_listener = self.register_renderer_method("addEventListener", "dom", ctx);
+            let event = ctx.ast.expression_string_literal(SPAN, event.to_string());
+            let mut call =
+                call_expression(add_event_listener, [elem_ref(ctx), event, handler], ctx);
+            *call.span_mut() = span;
+            results.exprs.insert(0, call);
+            return;
+        }
+        if let Some(event) = key.strip_prefix("oncapture:") {
+            let add_event_listener = static_member(elem_ref(ctx), "addEventListener", ctx);
+            let event = ctx.ast.expression_string_literal(SPAN, event.to_string());
+            let capture = ctx.ast.expression_boolean_literal(SPAN, true);
+            let mut call = call_expression(add_event_listener, [event, handler, capture], ctx);
+            *call.span_mut() = span;
+            results.exprs.push(call);
+            return;
+        }
+
+        let event = key[2..].to_lowercase();
+        // handlers that are known to be functions can be attached as they are
+        let resolvable = handler.is_function()
+            || matches!(&handler, ast::Expression::Identifier(ident)
+                if self.function_bindings.is_function(&ident.name, ctx));
+        // `[handler, data]` calls the handler with the data as its first argument
+        let (handler, data, listed) = match handler {
+            ast::Expression::ArrayExpression(mut array) => {
+                let well_formed = (1..=2).contains(&array.elements.len())
+                    && array.elements.iter().all(|element| element.is_expression());
+                if !well_formed {
+                    self.errors
+                        .push(diagnostics::invalid_event_handler_array(key, array.span));
+                    return;
+                }
+                let mut elements = array
+                    .elements
+                    .iter_mut()
+                    .filter_map(|element| element.as_expression_mut())
+                    .map(|expr| ctx.ast.move_expression(expr));
+                let Some(handler) = elements.next() else {
+                    return;
+                };
+                (handler, elements.next(), true)
+            }
+            handler => (handler, None, false),
+        };
+        let event_literal =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_string_literal(SPAN, event.clone());
+
+        let delegate = self.config.delegate_events
+            && (DELEGATED_EVENTS.contains(&event.as_str())
+                || self.config.delegated_events.contains(&event));
+        if delegate {
+            // only delegated events can be replayed after hydration
+            results.has_hydratable_event = true;
+            if !self.events.contains(&event) {
+                self.events.push(event.clone());
+            }
+            if listed || resolvable {
+                if let Some(data) = data {
+                    let data_key = format!("$${}Data", event);
+                    let mut assign = assign_member(elem_ref(ctx), &data_key, data, ctx);
+                    *assign.span_mut() = span;
+                    results.exprs.insert(0, assign);
+                }
+                let mut assign =
+                    assign_member(elem_ref(ctx), &format!("$${}", event), handler, ctx);
+                *assign.span_mut() = span;
+                results.exprs.insert(0, assign);
+            } else {
+                let add_event_listener =
+                    self.register_renderer_method("addEventListener", "dom", ctx);
+                let delegated = ctx.ast.expression_boolean_literal(SPAN, true);
+                let mut call = call_expression(
+                    add_event_listener,
+                    [elem_ref(ctx), event_literal(ctx), handler, delegated],
+                    ctx,
+                );
+                *call.span_mut() = span;
+                results.exprs.insert(0, call);
+            }
+            return;
+        }
+
+        let mut call = match listed || resolvable {
+            true => {
+                let handler = match data {
+                    Some(data) => {
+                        let event_arg = ctx.ast.atom("e");
+                        let call = call_expression(
+                            handler,
+                            [
+                                data,
+                                ctx.ast
+                                    .expression_identifier_reference(SPAN, event_arg.clone()),
+                            ],
+                            ctx,
+                        );
+                        arrow_function(ctx.ast.vec1(formal_parameter(event_arg, ctx)), call, ctx)
+                    }
+                    None => handler,
+                };
+                let add_event_listener = static_member(elem_ref(ctx), "addEventListener", ctx);
+                call_expression(add_event_listener, [event_literal(ctx), handler], ctx)
+            }
+            false => {
+                let add_event_listener =
+                    self.register_renderer_method("addEventListener", "dom", ctx);
+                call_expression(
+                    add_event_listener,
+                    [elem_ref(ctx), event_literal(ctx), handler],
+                    ctx,
+                )
+            }
+        };
+        *call.span_mut() = span;
+        results.exprs.insert(0, call);
+    }
+
+    /// Hand the element to a ref, which is either a callback or a variable to assign it to, using
+    /// the `use` helper of the renderer that created the element
+    pub(crate) fn transform_ref(
+        &mut self,
+        renderer: &str,
+        elem: Atom<'a>,
+        value: ast::Expression<'a>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let value = strip_ts_wrappers(value, ctx);
+        let use_fn = self.register_renderer_method("use", renderer, ctx);
+        let elem_ref =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, elem.clone());
+
+        if is_const_binding(&value, ctx) || value.is_function() {
+            let call = call_expression(use_fn, [value, elem_ref(ctx)], ctx);
+            results.exprs.insert(0, call);
+            return;
+        }
+
+        let ref_id = ctx
+            .generate_uid_in_current_scope("ref$", SymbolFlags::FunctionScopedVariable)
+            .name;
+        let ref_ref = |ctx: &TraverseCtx<'a>| {
+            ctx.ast
+                .expression_identifier_reference(SPAN, ref_id.clone())
+        };
+        let is_function = ctx.ast.expression_binary(
+            SPAN,
+            ctx.ast
+                .expression_unary(SPAN, UnaryOperator::Typeof, ref_ref(ctx)),
+            BinaryOperator::StrictEquality,
+            ctx.ast.expression_string_literal(SPAN, "function"),
+        );
+        let call = call_expression(use_fn, [ref_ref(ctx), elem_ref(ctx)], ctx);
+        let target = assignment_target(value.clone_in(ctx.ast.allocator));
+        // the reference is declared right before it is checked, like the babel plugin does
+        let declaration = assign_identifier(ref_id.clone(), value, ctx);
+        let expr = match target {
+            // assign the element when the ref isn't a callback
+            Some(target) => {
+                let assign = ctx.ast.expression_assignment(
+                    SPAN,
+                    AssignmentOperator::Assign,
+                    target,
+                    elem_ref(ctx),
+                );
+                ctx.ast
+                    .expression_conditional(SPAN, is_function, call, assign)
+            }
+            None => ctx
+                .ast
+                .expression_logical(SPAN, is_function, LogicalOperator::And, call),
+        };
+        results.exprs.insert(0, expr);
+        results.exprs.insert(0, declaration);
+        results.inline_declarations.push(ref_id);
+    }
+
+    /// Process children and append their templates to the parent
+    fn transform_children_dom(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let tag_name = results.tag_name.clone().unwrap_or_default();
+        let multi = check_length(&el.children);
+        let children = el
+            .children
+            .iter_mut()
+            .filter(|child| is_meaningful_child(child))
+            .collect::<Vec<_>>();
+        let (skip_ids, last_element) = {
+            let view = children.iter().map(|child| &**child).collect::<Vec<_>>();
+            let skip_ids = (0..view.len())
+                .map(|index| results.id.is_none() || !self.detect_expressions(&view, index))
+                .collect::<Vec<_>>();
+            // when hydrating, the closing tags are needed to match the server rendered markup
+            let last_element = match self.config.hydratable {
+                true => view.len().checked_sub(1),
+                false => find_last_element(&view),
+            };
+            (skip_ids, last_element)
+        };
+
+        let mut child_nodes: Vec<TransformResult<'a>> = Vec::new();
+        // the children in the source, which the code walking to them and inserting them maps to
+        let mut child_spans = Vec::new();
+        for (index, child) in children.into_iter().enumerate() {
+            let span = child.span();
+            let info = TransformInfo {
+                to_be_closed: results.to_be_closed.clone(),
+                last_element: Some(index) == last_element,
+                skip_id: skip_ids[index],
+                do_not_escape: tag_name == "style" || tag_name == "script",
+                ..Default::default()
+            };
+            let Some(transformed) = self.transform_node(child, ctx, &info) else {
+                continue;
+            };
+            // adjacent texts end up in a single text node
+            match child_nodes.last_mut() {
+                Some(prev) if transformed.text && prev.text => {
+                    if let (Some(prev_template), Some(template)) =
+                        (&mut prev.template, &transformed.template)
+                    {
+                        prev_template.push_str(template);
+                    }
+                }
+                _ => {
+                    child_nodes.push(transformed);
+                    child_spans.push(span);
+                }
+            }
+        }
+
+        // the closest following sibling that can be used as the insertion point
+        let mut next_ids = vec![None; child_nodes.len()];
+        for index in (0..child_nodes.len().saturating_sub(1)).rev() {
+            next_ids[index] = child_nodes[index + 1]
+                .id
+                .clone()
+                .or_else(|| next_ids[index + 1].clone());
+        }
+        let wrapped = (0..child_nodes.len())
+            .map(|index| wrapped_by_text(&child_nodes, index))
+            .collect::<Vec<_>>();
+
+        // the server renders every inserted expression between a pair of markers to hydrate
+        let markers = self.config.hydratable && multi;
+
+        let mut temp_path = results.id.clone();
+        let mut walk_index = 0;
+        // the marker shared by consecutive expressions between the same texts
+        let mut next_placeholder: Option<Atom<'a>> = None;
+        let mut child_post_exprs = Vec::new();
+        for (index, (mut child, span)) in child_nodes.into_iter().zip(child_spans).enumerate() {
+            if let (Some(template), Some(child_template)) = (&mut results.template, &child.template)
+            {
+                template.push_str(child_template);
+            }
+
+            // the head is left as the server rendered it
+            if self.config.hydratable && child.tag_name.as_deref() == Some("head") {
+                let no_hydration = self.no_hydration_dom(ctx);
+                results.exprs.push(no_hydration);
+                continue;
+            }
+            if let (Some(child_id), Some(path)) = (child.id.clone(), &temp_path) {
+                let mut declarator =
+                    walk_declarator(child_id.clone(), path.clone(), walk_index, ctx);
+                if let Some(walk) = &mut declarator.init {
+                    *walk.span_mut() = span;
+                }
+                // the children of `<html>` are matched by their tag, as the browser may have
+                // inserted other nodes between them
+                if self.config.hydratable && tag_name == "html" {
+                    if let (Some(walk), Some(child_tag)) = (declarator.init.take(), &child.tag_name)
+                    {
+                        let get_next_match =
+                            self.register_renderer_method("getNextMatch", "dom", ctx);
+                        let child_tag = ctx
+                            .ast
+                            .expression_string_literal(SPAN, ctx.ast.atom(child_tag));
+                        declarator.init =
+                            Some(call_expression(get_next_match, [walk, child_tag], ctx));
+                    }
+                }
+                results.declarations.push(declarator);
+                results.declarations.extend(child.declarations);
+                results.exprs.extend(child.exprs);
+                results
+                    .inline_declarations
+                    .extend(child.inline_declarations);
+                results.dynamics.extend(child.dynamics);
+                child_post_exprs.extend(child.post_exprs);
+                results.is_import_node |= child.is_import_node;
+                results.has_hydratable_event |= child.has_hydratable_event;
+                temp_path = Some(child_id);
+                next_placeholder = None;
+                walk_index += 1;
+            } else if !child.exprs.is_empty() {
+                let (Some(parent_id), Some(mut path)) = (results.id.clone(), temp_path.clone())
+                else {
+                    continue;
+                };
+                let insert = self.register_renderer_method("insert", "dom", ctx);
+                let mut args = vec![
+                    ctx.ast.expression_identifier_reference(SPAN, parent_id),
+                    child.exprs.remove(0),
+                ];
+                if markers || wrapped[index] {
+                    if markers {
+                        // the opening marker of the server rendered content
+                        path = self
+                            .create_placeholder_dom(results, path, walk_index, "$", ctx)
+                            .0;
+                        walk_index += 1;
+                    }
+                    // an expression between texts needs a marker to be inserted before
+                    let (marker, content) = match next_placeholder.clone() {
+                        Some(marker) => (marker, None),
+                        None => {
+                            let placeholder = self.create_placeholder_dom(
+                                results,
+                                path,
+                                walk_index,
+                                if markers { "/" } else { "" },
+                                ctx,
+                            );
+                            walk_index += 1;
+                            placeholder
+                        }
+                    };
+                    args.push(
+                        ctx.ast
+                            .expression_identifier_reference(SPAN, marker.clone()),
+                    );
+                    if let Some(content) = content {
+                        args.push(ctx.ast.expression_identifier_reference(SPAN, content));
+                    }
+                    temp_path = Some(marker.clone());
+                    if !markers {
+                        next_placeholder = Some(marker);
+                    }
+                } else if multi {
+                    args.push(match next_ids[index].clone() {
+                        Some(next_id) => ctx.ast.expression_identifier_reference(SPAN, next_id),
+                        None => ctx.ast.expression_null_literal(SPAN),
+                    });
+                }
+                let mut call = call_expression(insert, args, ctx);
+                *call.span_mut() = span;
+                results.exprs.push(call);
+            } else {
+                next_placeholder = None;
+            }
+        }
+        // effects of the children run before the ones of the element
+        let post_exprs = std::mem::replace(
+            &mut results.post_exprs,
+            ctx.ast.vec_from_iter(child_post_exprs),
+        );
+        results.post_exprs.extend(post_exprs);
+    }
+
+    /// create the component that keeps the client from hydrating a part of the page
+    fn no_hydration_dom(&mut self, ctx: &mut TraverseCtx<'a>) -> ast::Expression<'a> {
+        let create_component = self.register_import_method("createComponent", ctx);
+        let no_hydration = self.register_renderer_method("NoHydration", "dom", ctx);
+        let props = ctx.ast.expression_object(SPAN, ctx.ast.vec(), None);
+        call_expression(create_component, [no_hydration, props], ctx)
+    }
+
+    /// Add a comment node to the template and declare a reference to it, returning the reference
+    /// and, for the closing marker of hydrated content, the reference to the current content
+    fn create_placeholder_dom(
+        &mut self,
+        results: &mut TransformResult<'a>,
+        path: Atom<'a>,
+        walk_index: usize,
+        kind: &str,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> (Atom<'a>, Option<Atom<'a>>) {
+        let marker = ctx
+            .generate_uid_in_current_scope("el$", SymbolFlags::FunctionScopedVariable)
+            .name;
+        if let Some(template) = &mut results.template {
+            template.push_str(&format!("<!{}>", kind));
+        }
+        if !(self.config.hydratable && kind == "/") {
+            results
+                .declarations
+                .push(walk_declarator(marker.clone(), path, walk_index, ctx));
+            return (marker, None);
+        }
+
+        let content = ctx
+            .generate_uid_in_current_scope("co$", SymbolFlags::FunctionScopedVariable)
+            .name;
+        let get_next_marker = self.register_renderer_method("getNextMarker", "dom", ctx);
+        let next_sibling = static_member(
+            ctx.ast.expression_identifier_reference(SPAN, path),
+            "nextSibling",
+            ctx,
+        );
+        let init = call_expression(get_next_marker, [next_sibling], ctx);
+        let binding = |name: Atom<'a>| {
+            Some(ctx.ast.binding_pattern(
+                ctx.ast.binding_pattern_kind_binding_identifier(SPAN, name),
+                NONE,
+                false,
+            ))
+        };
+        let pattern = ctx.ast.binding_pattern(
+            ctx.ast.binding_pattern_kind_array_pattern(
+                SPAN,
+                ctx.ast
+                    .vec_from_iter([binding(marker.clone()), binding(content.clone())]),
+                NONE,
+            ),
+            NONE,
+            false,
+        );
+        results.declarations.push(ctx.ast.variable_declarator(
+            SPAN,
+            ast::VariableDeclarationKind::Var,
+            pattern,
+            Some(init),
+            false,
+        ));
+        (marker, Some(content))
+    }
+
+    /// whether anything inside of the element needs to be updated after cloning the template
+    fn has_expressions_dom(&self, el: &ast::JSXElement<'a>) -> bool {
+        if el
+            .opening_element
+            .attributes
             .iter()
-            .filter_map(|child| self.transform_node(child, ctx, &info)?.template)
-            .collect::<String>()
+            .any(attribute_needs_element)
+        {
+            return true;
+        }
+        let tag_name = get_tag_name(el);
+        if self.config.context_to_custom_elements
+            && (tag_name == "slot" || is_custom_element(&tag_name, el))
+        {
+            return true;
+        }
+
+        let children = el
+            .children
+            .iter()
+            .filter(|child| is_meaningful_child(child))
+            .collect::<Vec<_>>();
+        !children.is_empty() && self.detect_expressions(&children, 0)
     }
+
+    /// whether the children from the index onwards contain expressions, in which case the child
+    /// needs a reference to be used as an insertion point or to reach the following siblings
+    fn detect_expressions(&self, children: &[&ast::JSXChild<'a>], index: usize) -> bool {
+        let is_expression = |child: &ast::JSXChild<'a>| match child {
+            ast::JSXChild::ExpressionContainer(container) => container
+                .expression
+                .as_expression()
+                .is_some_and(|expr| static_expression_value(expr).is_none()),
+            ast::JSXChild::Spread(_) => true,
+            _ => false,
+        };
+
+        if index > 0 && is_expression(children[index - 1]) {
+            return true;
+        }
+        children[index..].iter().any(|child| match child {
+            ast::JSXChild::Element(el) => {
+                is_component(&get_tag_name(el)) || self.has_expressions_dom(el)
+            }
+            child => is_expression(child),
+        })
+    }
+}
+
+/// declare a reference to the node reached from the previous one
+fn walk_declarator<'a>(
+    id: Atom<'a>,
+    path: Atom<'a>,
+    walk_index: usize,
+    ctx: &TraverseCtx<'a>,
+) -> ast::VariableDeclarator<'a> {
+    let walk = static_member(
+        ctx.ast.expression_identifier_reference(SPAN, path),
+        match walk_index {
+            0 => "firstChild",
+            _ => "nextSibling",
+        },
+        ctx,
+    );
+    var_declarator(id, walk, ctx)
+}
+
+/// whether the closest nodes on both sides of the child are texts, in which case the inserted
+/// content needs a marker to not get merged into them
+fn wrapped_by_text(children: &[TransformResult], index: usize) -> bool {
+    fn closest_is_text<'r, 'a: 'r>(
+        mut nodes: impl Iterator<Item = &'r TransformResult<'a>>,
+    ) -> bool {
+        nodes
+            .find(|node| node.text || node.id.is_some())
+            .is_some_and(|node| node.text)
+    }
+    closest_is_text(children[..index].iter().rev()) && closest_is_text(children[index + 1..].iter())
+}
+
+/// find the index of the last child that ends up as a node in the template
+fn find_last_element(children: &[&ast::JSXChild]) -> Option<usize> {
+    children.iter().rposition(|child| match child {
+        ast::JSXChild::Text(_) => true,
+        ast::JSXChild::Element(el) => !is_component(&get_tag_name(el)),
+        ast::JSXChild::ExpressionContainer(container) => container
+            .expression
+            .as_expression()
+            .is_some_and(|expr| static_expression_value(expr).is_some()),
+        _ => false,
+    })
+}
+
+/// whether setting the attribute needs a reference to the element
+fn attribute_needs_element(attr: &ast::JSXAttributeItem) -> bool {
+    let ast::JSXAttributeItem::Attribute(attr) = attr else {
+        return true;
+    };
+    let (namespace, name) = match &attr.name {
+        ast::JSXAttributeName::Identifier(ident) => (None, ident.name.as_str()),
+        ast::JSXAttributeName::NamespacedName(name) => (
+            Some(name.namespace.name.as_str()),
+            name.property.name.as_str(),
+        ),
+    };
+    let static_value = match &attr.value {
+        None | Some(ast::JSXAttributeValue::StringLiteral(_)) => true,
+        Some(ast::JSXAttributeValue::ExpressionContainer(container)) => container
+            .expression
+            .as_expression()
+            .is_some_and(|expr| static_expression_value(expr).is_some()),
+        Some(_) => false,
+    };
+    match namespace {
+        // boolean attributes with a known value are part of the template
+        Some("bool") => !static_value,
+        Some(namespace) if RESERVED_NAMESPACES.contains(&namespace) => true,
+        _ => CHILD_PROPERTIES.contains(&name) || !static_value,
+    }
+}
+
+fn is_custom_element(tag_name: &str, el: &ast::JSXElement) -> bool {
+    tag_name.contains('-')
+        || el.opening_element.attributes.iter().any(|attr| {
+            matches!(attr, ast::JSXAttributeItem::Attribute(attr)
+                if matches!(&attr.name, ast::JSXAttributeName::Identifier(ident) if ident.name == "is"))
+        })
+}
+
+/// turn an attribute name of a custom element into the name of its property
+fn to_property_name(name: &str) -> String {
+    let mut property = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.to_lowercase().chars() {
+        match c {
+            '-' => upper = true,
+            c if upper => {
+                property.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => property.push(c),
+        }
+    }
+    property
+}
+
+/// whether an attribute value has to be quoted in the template
+fn needs_quotes(value: &str) -> bool {
+    value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '\'' | '"' | '`' | '=' | '<' | '>'))
+}
+
+fn push_attribute_name(results: &mut TransformResult, name: &str, needs_spacing: bool) {
+    if let Some(template) = &mut results.template {
+        if needs_spacing {
+            template.push(' ');
+        }
+        template.push_str(name);
+    }
+}
+
+/// get the names of the properties of an object literal, if they are all known at compile time
+fn static_object_keys(obj: &ast::ObjectExpression) -> Option<Vec<String>> {
+    obj.properties
+        .iter()
+        .map(|prop| match prop {
+            ast::ObjectPropertyKind::ObjectProperty(prop)
+                if !prop.computed && !prop.method && prop.kind == ast::PropertyKind::Init =>
+            {
+                match &prop.key {
+                    ast::PropertyKey::StaticIdentifier(ident) => Some(ident.name.to_string()),
+                    ast::PropertyKey::StringLiteral(lit) => Some(lit.value.to_string()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// split `style` and `classList` objects into an attribute per property, so that each of them can
+/// be inlined into the template or updated on its own
+fn expand_attribute_objects<'a>(
+    attributes: Vec<Attribute<'a>>,
+    ctx: &mut TraverseCtx<'a>,
+) -> Vec<Attribute<'a>> {
+    let mut expanded = Vec::with_capacity(attributes.len());
+    for mut attr in attributes {
+        let span = attr.span;
+        let keys = match (attr.key.as_str(), &attr.value) {
+            ("style", Some(ast::Expression::ObjectExpression(obj))) => static_object_keys(obj),
+            ("classList", Some(ast::Expression::ObjectExpression(obj))) => static_object_keys(obj)
+                .filter(|keys| !keys.iter().any(|key| key.contains([' ', ':']))),
+            _ => None,
+        };
+        let Some(keys) = keys else {
+            expanded.push(attr);
+            continue;
+        };
+        let Some(ast::Expression::ObjectExpression(obj)) = &mut attr.value else {
+            continue;
+        };
+
+        let namespace = match attr.key.as_str() {
+            "style" => "style",
+            _ => "class",
+        };
+        for (key, prop) in keys.into_iter().zip(obj.properties.iter_mut()) {
+            let ast::ObjectPropertyKind::ObjectProperty(prop) = prop else {
+                continue;
+            };
+            let value = ctx.ast.move_expression(&mut prop.value);
+            // classes that are always on go straight into the `class` attribute
+            if namespace == "class" {
+                match static_truthiness(&value) {
+                    Some(true) => {
+                        expanded.push(Attribute {
+                            key: "class".to_string(),
+                            value: Some(ctx.ast.expression_string_literal(SPAN, key)),
+                            container: false,
+                            span,
+                        });
+                        continue;
+                    }
+                    Some(false) => continue,
+                    None => {}
+                }
+            }
+            expanded.push(Attribute {
+                key: format!("{}:{}", namespace, key),
+                value: Some(value),
+                container: true,
+                span,
+            });
+        }
+    }
+    expanded
+}
+
+/// combine the `class` and `className` attributes into the first of them
+fn merge_class_attributes<'a>(
+    mut attributes: Vec<Attribute<'a>>,
+    ctx: &TraverseCtx<'a>,
+) -> Vec<Attribute<'a>> {
+    let indices = attributes
+        .iter()
+        .enumerate()
+        .filter(|(_, attr)| attr.key == "class" || attr.key == "className")
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if indices.len() < 2 {
+        return attributes;
+    }
+
+    let mut quasis = vec![String::new()];
+    let mut values = Vec::new();
+    for (i, index) in indices.iter().enumerate() {
+        let separator = match i == indices.len() - 1 {
+            true => "",
+            false => " ",
+        };
+        let value = attributes[*index].value.take();
+        let text = value
+            .as_ref()
+            .map_or(Some(String::new()), static_expression_value);
+        match (text, value) {
+            (Some(text), _) => {
+                if let Some(quasi) = quasis.last_mut() {
+                    quasi.push_str(&text);
+                    quasi.push_str(separator);
+                }
+            }
+            (None, Some(value)) => {
+                values.push(ctx.ast.expression_logical(
+                    SPAN,
+                    value,
+                    LogicalOperator::Or,
+                    ctx.ast.expression_string_literal(SPAN, ""),
+                ));
+                quasis.push(separator.to_string());
+            }
+            (None, None) => {}
+        }
+    }
+
+    let first = indices[0];
+    attributes[first].container = !values.is_empty();
+    attributes[first].value = Some(match values.is_empty() {
+        true => ctx.ast.expression_string_literal(SPAN, quasis.remove(0)),
+        false => template_literal(quasis, values, ctx),
+    });
+    attributes
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| *index == first || !indices.contains(index))
+        .map(|(_, attr)| attr)
+        .collect()
 }
@@ -1,20 +1,436 @@
-use oxc::{ast::ast, span::SPAN};
+use oxc::{
+    allocator::Vec as OxcVec,
+    ast::{ast, NONE},
+    semantic::SymbolFlags,
+    span::SPAN,
+    syntax::operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+};
 use oxc_traverse::TraverseCtx;
 
 use crate::{
-    shared::transform::{TemplateCreationCtx, TransformResult},
-    Config,
+    dom::element::SetAttrOptions,
+    shared::{
+        imports::ImportManager,
+        transform::{DynamicAttr, JsxTransform, Template, TemplateCreationCtx, TransformResult},
+        utils::{
+            arrow_function, arrow_function_block, assign_identifier, assign_member,
+            call_expression, escape_template, formal_parameter, iife, object_property,
+            static_member, var_declarator,
+        },
+    },
+    Config, OutputType,
 };
 
 impl<'a> TransformResult<'a> {
     pub fn create_template_dom(
-        &self,
+        mut self,
         config: &Config,
         traverse_ctx: &mut TraverseCtx<'a>,
         creation_ctx: &mut TemplateCreationCtx<'a>,
+        imports: &mut ImportManager<'a>,
         wrap: bool,
     ) -> ast::Expression<'a> {
-        // TODO
-        traverse_ctx.ast.expression_null_literal(SPAN)
+        let Some(id) = self.id.clone() else {
+            // a static element doesn't need a reference, so it can be cloned in place
+            if let Some(init) =
+                self.register_template_dom(config, traverse_ctx, creation_ctx, imports)
+            {
+                return init;
+            }
+            let expr = match self.exprs.is_empty() {
+                true => traverse_ctx.ast.expression_null_literal(SPAN),
+                false => self.exprs.remove(0),
+            };
+            if let (true, Some(memo_wrapper)) = (wrap && self.dynamic, &config.memo_wrapper) {
+                let memo = imports.add(memo_wrapper, &config.module_name, traverse_ctx);
+                return call_expression(
+                    traverse_ctx.ast.expression_identifier_reference(SPAN, memo),
+                    [expr],
+                    traverse_ctx,
+                );
+            }
+            return expr;
+        };
+
+        if let Some(init) = self.register_template_dom(config, traverse_ctx, creation_ctx, imports)
+        {
+            self.declarations.insert(
+                0,
+                traverse_ctx.ast.variable_declarator(
+                    SPAN,
+                    ast::VariableDeclarationKind::Var,
+                    traverse_ctx.ast.binding_pattern(
+                        traverse_ctx
+                            .ast
+                            .binding_pattern_kind_binding_identifier(SPAN, id.clone()),
+                        NONE,
+                        false,
+                    ),
+                    Some(init),
+                    false,
+                ),
+            );
+        }
+
+        if self.exprs.is_empty() && self.declarations.len() == 1 {
+            if let Some(init) = self.declarations.remove(0).init {
+                return init;
+            }
+        }
+
+        let mut statements = traverse_ctx.ast.vec();
+        statements.push(traverse_ctx.ast.statement_declaration(
+            traverse_ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                self.declarations,
+                false,
+            ),
+        ));
+        statements.extend(TransformResult::expr_statements(
+            self.exprs,
+            &self.inline_declarations,
+            traverse_ctx,
+        ));
+        statements.push(traverse_ctx.ast.statement_return(
+            SPAN,
+            Some(traverse_ctx.ast.expression_identifier_reference(SPAN, id)),
+        ));
+        iife(statements, traverse_ctx)
+    }
+
+    /// register the template of the result, returning the expression that clones it, or that
+    /// claims the next server rendered element when hydrating
+    fn register_template_dom(
+        &self,
+        config: &Config,
+        traverse_ctx: &mut TraverseCtx<'a>,
+        creation_ctx: &mut TemplateCreationCtx<'a>,
+        imports: &mut ImportManager<'a>,
+    ) -> Option<ast::Expression<'a>> {
+        let template = self
+            .template
+            .as_ref()
+            .filter(|t| !t.is_empty() && !self.text)?;
+        if self.skip_template && !config.hydratable {
+            return None;
+        }
+        let get_next_element = |traverse_ctx: &mut TraverseCtx<'a>,
+                                imports: &mut ImportManager<'a>,
+                                args: Vec<ast::Expression<'a>>| {
+            let get_next_element = imports.add(
+                "getNextElement",
+                config.renderer_module("dom"),
+                traverse_ctx,
+            );
+            call_expression(
+                traverse_ctx
+                    .ast
+                    .expression_identifier_reference(SPAN, get_next_element),
+                args,
+                traverse_ctx,
+            )
+        };
+        // a skipped template is already in the document, so there is nothing to clone
+        if self.skip_template {
+            return Some(get_next_element(traverse_ctx, imports, Vec::new()));
+        }
+
+        let template_id = match creation_ctx
+            .templates
+            .iter()
+            .find(|t| t.renderer == OutputType::Dom && &t.template == template)
+        {
+            Some(existing) => existing.id.clone(),
+            None => {
+                let id = traverse_ctx
+                    .generate_uid_in_root_scope("tmpl$", SymbolFlags::FunctionScopedVariable)
+                    .name;
+                creation_ctx.templates.push(Template {
+                    id: id.clone(),
+                    template: template.clone(),
+                    renderer: OutputType::Dom,
+                    is_svg: self.is_svg,
+                    is_import_node: self.is_import_node,
+                    parts: Vec::new(),
+                });
+                id
+            }
+        };
+
+        let template_id = traverse_ctx
+            .ast
+            .expression_identifier_reference(SPAN, template_id);
+        Some(match config.hydratable {
+            true => get_next_element(traverse_ctx, imports, vec![template_id]),
+            false => traverse_ctx.ast.expression_call(
+                SPAN,
+                template_id,
+                NONE,
+                traverse_ctx.ast.vec(),
+                false,
+            ),
+        })
+    }
+}
+
+impl<'a> JsxTransform<'a> {
+    /// hoist the collected templates to the top of the program
+    pub fn append_templates_dom(
+        &mut self,
+        program: &mut ast::Program<'a>,
+        templates: Vec<Template<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let mut declarators: OxcVec<'a, ast::VariableDeclarator<'a>> = ctx.ast.vec();
+        for template in templates
+            .into_iter()
+            .filter(|t| t.renderer == OutputType::Dom)
+        {
+            let quasi = ctx.ast.template_element(
+                SPAN,
+                true,
+                ast::TemplateElementValue {
+                    raw: ctx.ast.atom(&escape_template(&template.template)),
+                    cooked: Some(ctx.ast.atom(&template.template)),
+                },
+            );
+            let mut args =
+                vec![ctx
+                    .ast
+                    .expression_template_literal(SPAN, ctx.ast.vec1(quasi), ctx.ast.vec())];
+            if template.is_import_node || template.is_svg {
+                args.push(
+                    ctx.ast
+                        .expression_boolean_literal(SPAN, template.is_import_node),
+                );
+                args.push(ctx.ast.expression_boolean_literal(SPAN, template.is_svg));
+            }
+            let callee = self.register_renderer_method("template", "dom", ctx);
+            if let ast::Expression::Identifier(ident) = &callee {
+                if !self
+                    .pure_callees
+                    .iter()
+                    .any(|name| name == ident.name.as_str())
+                {
+                    self.pure_callees.push(ident.name.to_string());
+                }
+            }
+            let init = call_expression(callee, args, ctx);
+            declarators.push(
+                ctx.ast.variable_declarator(
+                    SPAN,
+                    ast::VariableDeclarationKind::Var,
+                    ctx.ast.binding_pattern(
+                        ctx.ast
+                            .binding_pattern_kind_binding_identifier(SPAN, template.id),
+                        NONE,
+                        false,
+                    ),
+                    Some(init),
+                    false,
+                ),
+            );
+        }
+        if declarators.is_empty() {
+            return;
+        }
+
+        program.body.insert(
+            0,
+            ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                declarators,
+                false,
+            )),
+        );
+    }
+}
+
+impl<'a> JsxTransform<'a> {
+    /// register the delegated events with the runtime at the end of the program
+    pub fn append_events_dom(&mut self, program: &mut ast::Program<'a>, ctx: &mut TraverseCtx<'a>) {
+        if self.events.is_empty() {
+            return;
+        }
+        let events = std::mem::take(&mut self.events);
+        let events = ctx.ast.expression_array(
+            SPAN,
+            ctx.ast.vec_from_iter(events.into_iter().map(|event| {
+                ctx.ast.array_expression_element_expression(
+                    ctx.ast.expression_string_literal(SPAN, event),
+                )
+            })),
+            None,
+        );
+        let delegate_events = self.register_renderer_method("delegateEvents", "dom", ctx);
+        let call = call_expression(delegate_events, [events], ctx);
+        program.body.push(ctx.ast.statement_expression(SPAN, call));
+    }
+
+    /// wrap the updates of the dynamic attributes in a single effect, which keeps track of the
+    /// previous values so that only the changed attributes are set
+    pub fn wrap_dynamics_dom(
+        &mut self,
+        dynamics: Vec<DynamicAttr<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<ast::Expression<'a>> {
+        if dynamics.is_empty() {
+            return None;
+        }
+        let effect_wrapper = self.config.effect_wrapper.clone()?;
+        let effect = self.register_import_method(&effect_wrapper, ctx);
+
+        // the effect maps back to the first of the attributes it updates
+        let span = dynamics[0].span;
+        if dynamics.len() == 1 {
+            let dynamic = dynamics.into_iter().next()?;
+            let prev_value = ctx.ast.atom("_$p");
+            let value = boolean_class_value(&dynamic.key, dynamic.value, ctx);
+            let (params, value, prev_id) = match dynamic.key.as_str() {
+                "style" | "classList" => (
+                    ctx.ast.vec1(formal_parameter(prev_value.clone(), ctx)),
+                    value,
+                    Some(ctx.ast.expression_identifier_reference(SPAN, prev_value)),
+                ),
+                key if key.starts_with("style:") => (
+                    ctx.ast.vec1(formal_parameter(prev_value.clone(), ctx)),
+                    assign_identifier(prev_value.clone(), value, ctx),
+                    Some(ctx.ast.expression_identifier_reference(SPAN, prev_value)),
+                ),
+                _ => (ctx.ast.vec(), value, None),
+            };
+            let mut set = self.set_attr_dom(
+                &dynamic.elem,
+                &dynamic.key,
+                value,
+                SetAttrOptions {
+                    is_svg: dynamic.is_svg,
+                    is_ce: dynamic.is_ce,
+                    dynamic: true,
+                    prev_id,
+                    tag_name: &dynamic.tag_name,
+                },
+                ctx,
+            );
+            *set.span_mut() = span;
+            let arrow_fn = arrow_function(params, set, ctx);
+            let mut call = call_expression(effect, [arrow_fn], ctx);
+            *call.span_mut() = span;
+            return Some(call);
+        }
+
+        let prev_value = ctx.ast.atom("_p$");
+        let prev = |ctx: &TraverseCtx<'a>| {
+            ctx.ast
+                .expression_identifier_reference(SPAN, prev_value.clone())
+        };
+        let mut declarations = ctx.ast.vec();
+        let mut statements = ctx.ast.vec();
+        let mut properties = ctx.ast.vec();
+        for (index, dynamic) in dynamics.into_iter().enumerate() {
+            let id = numbered_id(index);
+            let var = ctx
+                .generate_uid_in_current_scope("v$", SymbolFlags::FunctionScopedVariable)
+                .name;
+            let var_ref =
+                |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, var.clone());
+            let value = boolean_class_value(&dynamic.key, dynamic.value, ctx);
+            declarations.push(var_declarator(var.clone(), value, ctx));
+            properties.push(object_property(
+                &id,
+                ctx.ast.expression_identifier_reference(SPAN, "undefined"),
+                ctx,
+            ));
+
+            let options = |prev_id| SetAttrOptions {
+                is_svg: dynamic.is_svg,
+                is_ce: dynamic.is_ce,
+                dynamic: true,
+                prev_id,
+                tag_name: &dynamic.tag_name,
+            };
+            let statement = match dynamic.key.as_str() {
+                // these helpers diff the values themselves and return the value to compare against
+                "style" | "classList" => {
+                    let prev_id = static_member(prev(ctx), &id, ctx);
+                    let mut set = self.set_attr_dom(
+                        &dynamic.elem,
+                        &dynamic.key,
+                        var_ref(ctx),
+                        options(Some(prev_id)),
+                        ctx,
+                    );
+                    *set.span_mut() = dynamic.span;
+                    assign_member(prev(ctx), &id, set, ctx)
+                }
+                key => {
+                    let prev_id = key.starts_with("style:").then(|| var_ref(ctx));
+                    let value = assign_member(prev(ctx), &id, var_ref(ctx), ctx);
+                    let mut set =
+                        self.set_attr_dom(&dynamic.elem, key, value, options(prev_id), ctx);
+                    *set.span_mut() = dynamic.span;
+                    let changed = ctx.ast.expression_binary(
+                        SPAN,
+                        var_ref(ctx),
+                        BinaryOperator::StrictInequality,
+                        static_member(prev(ctx), &id, ctx),
+                    );
+                    ctx.ast
+                        .expression_logical(SPAN, changed, LogicalOperator::And, set)
+                }
+            };
+            statements.push(ctx.ast.statement_expression(SPAN, statement));
+        }
+
+        statements.insert(
+            0,
+            ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                declarations,
+                false,
+            )),
+        );
+        statements.push(ctx.ast.statement_return(SPAN, Some(prev(ctx))));
+        let arrow_fn = arrow_function_block(
+            ctx.ast.vec1(formal_parameter(prev_value.clone(), ctx)),
+            statements,
+            ctx,
+        );
+        let initial = ctx.ast.expression_object(SPAN, properties, None);
+        let mut call = call_expression(effect, [arrow_fn, initial], ctx);
+        *call.span_mut() = span;
+        Some(call)
+    }
+}
+
+/// toggling a class needs a boolean, so other values are converted with `!!`
+fn boolean_class_value<'a>(
+    key: &str,
+    value: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    match value {
+        ast::Expression::BooleanLiteral(_) | ast::Expression::UnaryExpression(_) => value,
+        value if key.starts_with("class:") => {
+            let not = ctx
+                .ast
+                .expression_unary(SPAN, UnaryOperator::LogicalNot, value);
+            ctx.ast
+                .expression_unary(SPAN, UnaryOperator::LogicalNot, not)
+        }
+        value => value,
+    }
+}
+
+/// get a short property name for the previous value of the dynamic attribute at the index
+pub(crate) fn numbered_id(index: usize) -> String {
+    const LETTERS: &[u8] = b"etaoinshrdlucwmfygpbvkxjqz";
+    let letter = LETTERS[index % LETTERS.len()] as char;
+    match index / LETTERS.len() {
+        0 => letter.to_string(),
+        n => format!("{}{}", letter, n),
     }
 }
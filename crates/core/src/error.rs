@@ -0,0 +1,33 @@
+use std::fmt;
+
+use oxc::diagnostics::{GraphicalReportHandler, GraphicalTheme, OxcDiagnostic};
+
+/// the diagnostics of a source that couldn't be transformed, rendered with code frames pointing
+/// into the source when displayed
+#[derive(Debug)]
+pub struct TransformError {
+    pub diagnostics: Vec<OxcDiagnostic>,
+    source: String,
+}
+
+impl TransformError {
+    pub fn new(diagnostics: Vec<OxcDiagnostic>, source: &str) -> Self {
+        Self {
+            diagnostics,
+            source: source.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let handler = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor());
+        for diagnostic in &self.diagnostics {
+            let report = diagnostic.clone().with_source_code(self.source.clone());
+            handler.render_report(f, report.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for TransformError {}
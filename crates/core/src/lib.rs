@@ -1,26 +1,83 @@
-use std::convert::Infallible;
+use std::path::PathBuf;
 
 use oxc::{
-    allocator::Allocator, codegen::Codegen, parser::Parser, semantic::SemanticBuilder,
-    span::SourceType,
+    allocator::Allocator,
+    codegen::{CodeGenerator, CodegenOptions},
+    parser::Parser,
+    semantic::SemanticBuilder,
+    sourcemap::SourceMap,
 };
 
 pub mod config;
 mod dom;
+pub mod error;
 mod shared;
+mod source_map;
+mod ssr;
+mod universal;
 
 pub use config::*;
+pub use error::*;
+pub use oxc::{sourcemap, span::SourceType};
 use shared::{this_to_self::ThisToSelfTransform, transform::JsxTransform};
 
-pub fn transform(source: String, config: Config) -> Result<String, Infallible> {
+/// how the output of a file is generated
+#[derive(Default)]
+pub struct TransformOptions {
+    /// the name of the file, which the source map points to and whose extension tells how the
+    /// source is parsed
+    pub filename: Option<String>,
+    /// how the source is parsed, overriding the type implied by the filename
+    pub source_type: Option<SourceType>,
+    /// whether to generate a source map for the output
+    pub source_maps: bool,
+    /// the map of the source when it is the output of an earlier transform, which the map of
+    /// the output is chained to
+    pub input_source_map: Option<SourceMap>,
+}
+
+impl TransformOptions {
+    /// the source type given explicitly or implied by the extension of the file, falling back to
+    /// TypeScript with JSX
+    pub fn source_type(&self) -> SourceType {
+        self.source_type
+            .or_else(|| {
+                let filename = self.filename.as_deref()?;
+                SourceType::from_path(filename).ok()
+            })
+            .unwrap_or_else(SourceType::tsx)
+    }
+}
+
+pub struct TransformOutput {
+    pub code: String,
+    pub map: Option<SourceMap>,
+}
+
+pub fn transform(source: String, config: Config) -> Result<String, TransformError> {
+    transform_with_options(source, config, TransformOptions::default()).map(|output| output.code)
+}
+
+pub fn transform_with_options(
+    source: String,
+    config: Config,
+    options: TransformOptions,
+) -> Result<TransformOutput, TransformError> {
     let allocator = Allocator::default();
-    let source_type = SourceType::tsx();
+    let source_type = options.source_type();
 
     let parse_result = Parser::new(&allocator, &source, source_type).parse();
+    if parse_result.panicked || !parse_result.errors.is_empty() {
+        return Err(TransformError::new(parse_result.errors, &source));
+    }
     let mut program = parse_result.program;
     let semantic_result = SemanticBuilder::new(&source)
+        .with_check_syntax_error(true)
         .with_excess_capacity(2.0)
         .build(&program);
+    if !semantic_result.errors.is_empty() {
+        return Err(TransformError::new(semantic_result.errors, &source));
+    }
     let (symbols, scopes) = semantic_result.semantic.into_symbol_table_and_scope_tree();
 
     let mut this_transform = ThisToSelfTransform::new();
@@ -41,5 +98,78 @@ pub fn transform(source: String, config: Config) -> Result<String, Infallible> {
         scopes,
     );
 
-    Ok(Codegen::new().build(&program).code)
+    if !jsx_transform.errors.is_empty() {
+        return Err(TransformError::new(jsx_transform.errors, &source));
+    }
+    let source_map_path = options.source_maps.then(|| {
+        PathBuf::from(match options.filename.as_deref() {
+            Some(filename) => filename,
+            None => default_filename(source_type),
+        })
+    });
+    let output = CodeGenerator::new()
+        .with_options(CodegenOptions {
+            source_map_path,
+            ..CodegenOptions::default()
+        })
+        .build(&program);
+    let (code, map) = annotate_pure_calls(output.code, output.map, &jsx_transform.pure_callees);
+    let map = match (map, &options.input_source_map) {
+        (Some(map), Some(input_map)) => Some(source_map::chain(&map, input_map)),
+        (map, _) => map,
+    };
+    Ok(TransformOutput { code, map })
+}
+
+/// the name the source map gives to a source without a filename, with the extension of its type
+fn default_filename(source_type: SourceType) -> &'static str {
+    match (source_type.is_typescript(), source_type.is_jsx()) {
+        (true, true) => "input.tsx",
+        (true, false) => "input.ts",
+        (false, true) => "input.jsx",
+        (false, false) => "input.js",
+    }
+}
+
+/// marks the calls as free of side effects, so that unused templates can be dropped
+const PURE_ANNOTATION: &str = "/*#__PURE__*/";
+
+/// annotate the calls initializing the hoisted templates as pure. The codegen only prints the
+/// annotations found in the source, so they are inserted into the generated code, shifting the
+/// mappings that follow them on their line
+fn annotate_pure_calls(
+    code: String,
+    map: Option<SourceMap>,
+    callees: &[String],
+) -> (String, Option<SourceMap>) {
+    if callees.is_empty() {
+        return (code, map);
+    }
+    let patterns = callees
+        .iter()
+        .map(|callee| format!("= {}(", callee))
+        .collect::<Vec<_>>();
+    let mut annotated = String::with_capacity(code.len());
+    let mut insertions = Vec::new();
+    for (line_index, line) in code.split_inclusive('\n').enumerate() {
+        let mut offsets = patterns
+            .iter()
+            .flat_map(|pattern| line.match_indices(pattern.as_str()))
+            .map(|(offset, _)| offset + 2)
+            .collect::<Vec<_>>();
+        offsets.sort_unstable();
+        let mut rest = 0;
+        for offset in offsets {
+            annotated.push_str(&line[rest..offset]);
+            annotated.push_str(PURE_ANNOTATION);
+            rest = offset;
+            // columns of source maps count UTF-16 code units
+            let column = line[..offset].encode_utf16().count() as u32;
+            insertions.push((line_index as u32, column));
+        }
+        annotated.push_str(&line[rest..]);
+    }
+    let map =
+        map.map(|map| source_map::shift_columns(&map, &insertions, PURE_ANNOTATION.len() as u32));
+    (annotated, map)
 }
@@ -0,0 +1,421 @@
+use html_escape::decode_html_entities;
+use oxc::{
+    allocator::{CloneIn, Vec as OxcVec},
+    ast::ast,
+    semantic::SymbolFlags,
+    span::{Atom, SPAN},
+    syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::{
+    shared::{
+        transform::{JsxTransform, TransformInfo, TransformResult},
+        utils::{
+            arrow_function, assignment_target, call_expression, formal_parameter, get_tag_name,
+            is_const_binding, object_method, object_property, static_member, strip_ts_wrappers,
+            trim_whitespace, var_declarator, DynamicCheck,
+        },
+    },
+    OutputType,
+};
+
+impl<'a> JsxTransform<'a> {
+    /// Turn a component element into a `createComponent(Comp, props)` call
+    pub fn transform_component(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> TransformResult<'a> {
+        let tag_name = get_tag_name(el);
+        // control flow components are imported from the runtime unless they are declared locally
+        let tag = match self.config.built_ins.contains(&tag_name)
+            && ctx
+                .scopes()
+                .find_binding(ctx.current_scope_id(), &tag_name)
+                .is_none()
+        {
+            true => self.register_import_method(&tag_name, ctx),
+            false => component_tag(&el.opening_element.name, ctx),
+        };
+
+        let mut props: Vec<ast::Expression<'a>> = Vec::new();
+        let mut running_object = ctx.ast.vec();
+        let mut dynamic_spread = false;
+        for attr in el.opening_element.attributes.iter_mut() {
+            match attr {
+                ast::JSXAttributeItem::SpreadAttribute(spread) => {
+                    if !running_object.is_empty() {
+                        props.push(ctx.ast.expression_object(
+                            SPAN,
+                            std::mem::replace(&mut running_object, ctx.ast.vec()),
+                            None,
+                        ));
+                    }
+                    let argument = ctx.ast.move_expression(&mut spread.argument);
+                    let (argument, dynamic) = self.transform_spread_argument(argument, ctx);
+                    dynamic_spread |= dynamic;
+                    props.push(argument);
+                }
+                ast::JSXAttributeItem::Attribute(attr) => {
+                    let key = match &attr.name {
+                        ast::JSXAttributeName::Identifier(ident) => ident.name.to_string(),
+                        ast::JSXAttributeName::NamespacedName(name) => {
+                            format!("{}:{}", name.namespace.name, name.property.name)
+                        }
+                    };
+                    let value = match attr.value.take() {
+                        None => ctx.ast.expression_boolean_literal(SPAN, true),
+                        Some(ast::JSXAttributeValue::StringLiteral(lit)) => ctx
+                            .ast
+                            .expression_string_literal(lit.span, decode_html_entities(&lit.value)),
+                        Some(ast::JSXAttributeValue::ExpressionContainer(mut container)) => {
+                            match container.expression.as_expression_mut() {
+                                Some(expr) => ctx.ast.move_expression(expr),
+                                None => continue,
+                            }
+                        }
+                        Some(ast::JSXAttributeValue::Element(el)) => {
+                            ast::Expression::JSXElement(el)
+                        }
+                        Some(ast::JSXAttributeValue::Fragment(frag)) => {
+                            ast::Expression::JSXFragment(frag)
+                        }
+                    };
+
+                    if key == "ref" {
+                        // there is nothing to reference on the server
+                        if self.config.generate == OutputType::Ssr {
+                            continue;
+                        }
+                        if let Some(prop) = component_ref(value, ctx) {
+                            running_object.push(prop);
+                        }
+                        continue;
+                    }
+
+                    let is_dynamic = self.is_dynamic(
+                        &value,
+                        DynamicCheck {
+                            check_member: true,
+                            check_tags: true,
+                            ..Default::default()
+                        },
+                        ctx,
+                    );
+                    running_object.push(match is_dynamic {
+                        true => {
+                            let value = match value {
+                                ast::Expression::LogicalExpression(_)
+                                | ast::Expression::ConditionalExpression(_)
+                                    if self.config.wrap_conditionals
+                                        && self.config.memo_wrapper.is_some()
+                                        && self.config.generate != OutputType::Ssr =>
+                                {
+                                    self.transform_condition(value, true, false, ctx)
+                                }
+                                value => value,
+                            };
+                            let statements = getter_body(value, ctx);
+                            object_method(
+                                ast::PropertyKind::Get,
+                                &key,
+                                ctx.ast.vec(),
+                                statements,
+                                ctx,
+                            )
+                        }
+                        false => object_property(&key, value, ctx),
+                    });
+                }
+            }
+        }
+
+        if let Some((children, dynamic)) = self.transform_component_children(&mut el.children, ctx)
+        {
+            running_object.push(match dynamic {
+                true => {
+                    let statements = getter_body(children, ctx);
+                    object_method(
+                        ast::PropertyKind::Get,
+                        "children",
+                        ctx.ast.vec(),
+                        statements,
+                        ctx,
+                    )
+                }
+                false => object_property("children", children, ctx),
+            });
+        }
+
+        if !running_object.is_empty() || props.is_empty() {
+            props.push(ctx.ast.expression_object(SPAN, running_object, None));
+        }
+        let props = match props.len() > 1 || dynamic_spread {
+            true => {
+                let merge_props = self.register_import_method("mergeProps", ctx);
+                call_expression(merge_props, props, ctx)
+            }
+            false => props.remove(0),
+        };
+
+        let create_component = self.register_import_method("createComponent", ctx);
+        TransformResult {
+            exprs: ctx
+                .ast
+                .vec1(call_expression(create_component, [tag, props], ctx)),
+            ..TransformResult::new(ctx)
+        }
+    }
+
+    /// Transform the children of a component into the value of its `children` prop, returning
+    /// whether the value has to be read lazily
+    fn transform_component_children(
+        &mut self,
+        children: &mut OxcVec<'a, ast::JSXChild<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<(ast::Expression<'a>, bool)> {
+        let filtered = children
+            .iter_mut()
+            .filter(|child| match child {
+                ast::JSXChild::ExpressionContainer(container) => {
+                    !matches!(container.expression, ast::JSXExpression::EmptyExpression(_))
+                }
+                // text made of a line break and indentation only
+                ast::JSXChild::Text(text) => {
+                    !(text.value.starts_with(['\r', '\n']) && text.value.trim().is_empty())
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+        let multi = filtered.len() > 1;
+        let info = TransformInfo {
+            top_level: true,
+            component_child: true,
+            last_element: true,
+            ..Default::default()
+        };
+
+        let mut dynamic = false;
+        // whether the single child is an element or a fragment, which have to be created lazily
+        let mut needs_wrapping = false;
+        let mut transformed = Vec::new();
+        for child in filtered {
+            match child {
+                ast::JSXChild::Text(text) => {
+                    let value = trim_whitespace(&text.value);
+                    let value = decode_html_entities(&value);
+                    if !value.is_empty() {
+                        transformed.push(ctx.ast.expression_string_literal(text.span, value));
+                    }
+                }
+                child => {
+                    needs_wrapping = matches!(
+                        child,
+                        ast::JSXChild::Element(_) | ast::JSXChild::Fragment(_)
+                    );
+                    if let Some(result) = self.transform_node(child, ctx, &info) {
+                        let child_dynamic = result.dynamic;
+                        dynamic |= child_dynamic;
+                        let expr = result.create_template(
+                            &self.config,
+                            ctx,
+                            &mut self.template_ctx,
+                            &mut self.imports,
+                            multi,
+                        );
+                        // the array is read lazily as a whole on the server
+                        transformed.push(
+                            match multi && child_dynamic && self.config.generate == OutputType::Ssr
+                            {
+                                true => arrow_body(expr, ctx),
+                                false => expr,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        match transformed.len() {
+            0 => None,
+            1 => {
+                let child = transformed.remove(0);
+                if !needs_wrapping {
+                    return Some((child, dynamic));
+                }
+                let child = match child {
+                    ast::Expression::CallExpression(mut call)
+                        if call.arguments.is_empty()
+                            && !matches!(call.callee, ast::Expression::Identifier(_)) =>
+                    {
+                        ctx.ast.move_expression(&mut call.callee)
+                    }
+                    child => arrow_function(ctx.ast.vec(), child, ctx),
+                };
+                Some((child, true))
+            }
+            _ => {
+                let array = ctx.ast.expression_array(
+                    SPAN,
+                    ctx.ast.vec_from_iter(
+                        transformed
+                            .into_iter()
+                            .map(|expr| ctx.ast.array_expression_element_expression(expr)),
+                    ),
+                    None,
+                );
+                Some((arrow_function(ctx.ast.vec(), array, ctx), true))
+            }
+        }
+    }
+}
+
+/// get the expression referencing the component of the tag
+fn component_tag<'a>(name: &ast::JSXElementName<'a>, ctx: &TraverseCtx<'a>) -> ast::Expression<'a> {
+    fn member_expression<'a>(
+        member: &ast::JSXMemberExpression<'a>,
+        ctx: &TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let object = match &member.object {
+            ast::JSXMemberExpressionObject::IdentifierReference(ident) => ctx
+                .ast
+                .expression_identifier_reference(SPAN, ident.name.clone()),
+            ast::JSXMemberExpressionObject::MemberExpression(member) => {
+                member_expression(member, ctx)
+            }
+            ast::JSXMemberExpressionObject::ThisExpression(_) => ctx.ast.expression_this(SPAN),
+        };
+        static_member(object, &member.property.name, ctx)
+    }
+
+    match name {
+        ast::JSXElementName::Identifier(ident) => ctx
+            .ast
+            .expression_identifier_reference(SPAN, ident.name.clone()),
+        ast::JSXElementName::IdentifierReference(ident) => ctx
+            .ast
+            .expression_identifier_reference(SPAN, ident.name.clone()),
+        ast::JSXElementName::NamespacedName(name) => ctx.ast.expression_string_literal(
+            SPAN,
+            ctx.ast
+                .atom(&format!("{}:{}", name.namespace.name, name.property.name)),
+        ),
+        ast::JSXElementName::MemberExpression(member) => member_expression(member, ctx),
+        ast::JSXElementName::ThisExpression(_) => ctx.ast.expression_this(SPAN),
+    }
+}
+
+/// the statements of a getter returning the value, inlining the body of functions that are
+/// called right away or that wrap the value
+fn getter_body<'a>(
+    value: ast::Expression<'a>,
+    ctx: &mut TraverseCtx<'a>,
+) -> OxcVec<'a, ast::Statement<'a>> {
+    let value = match value {
+        ast::Expression::CallExpression(mut call)
+            if call.arguments.is_empty() && is_parameterless_arrow(&call.callee) =>
+        {
+            ctx.ast.move_expression(&mut call.callee)
+        }
+        value if is_parameterless_arrow(&value) => value,
+        value => return ctx.ast.vec1(ctx.ast.statement_return(SPAN, Some(value))),
+    };
+    let ast::Expression::ArrowFunctionExpression(mut arrow) = value else {
+        unreachable!();
+    };
+    let mut statements = std::mem::replace(&mut arrow.body.statements, ctx.ast.vec());
+    if arrow.expression {
+        if let Some(ast::Statement::ExpressionStatement(mut stmt)) = statements.pop() {
+            let expr = ctx.ast.move_expression(&mut stmt.expression);
+            statements.push(ctx.ast.statement_return(SPAN, Some(expr)));
+        }
+    }
+    statements
+}
+
+/// get the expression returned by an arrow function with an expression body
+fn arrow_body<'a>(expr: ast::Expression<'a>, ctx: &TraverseCtx<'a>) -> ast::Expression<'a> {
+    match expr {
+        ast::Expression::ArrowFunctionExpression(mut arrow) if arrow.expression => {
+            if let Some(ast::Statement::ExpressionStatement(stmt)) =
+                arrow.body.statements.first_mut()
+            {
+                return ctx.ast.move_expression(&mut stmt.expression);
+            }
+            ast::Expression::ArrowFunctionExpression(arrow)
+        }
+        expr => expr,
+    }
+}
+
+fn is_parameterless_arrow(expr: &ast::Expression) -> bool {
+    matches!(expr, ast::Expression::ArrowFunctionExpression(arrow)
+        if arrow.params.items.is_empty() && arrow.params.rest.is_none())
+}
+
+/// create the `ref` prop of a component, which is a method assigning the value when the ref
+/// isn't a callback
+fn component_ref<'a>(
+    value: ast::Expression<'a>,
+    ctx: &mut TraverseCtx<'a>,
+) -> Option<ast::ObjectPropertyKind<'a>> {
+    let value = strip_ts_wrappers(value, ctx);
+    if is_const_binding(&value, ctx) || value.is_function() {
+        return Some(object_property("ref", value, ctx));
+    }
+
+    let target = assignment_target(value.clone_in(ctx.ast.allocator));
+    if target.is_none() && !matches!(value, ast::Expression::CallExpression(_)) {
+        return None;
+    }
+    let param: Atom<'a> = ctx.ast.atom("r$");
+    let param_ref =
+        |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, param.clone());
+    let ref_id = ctx
+        .generate_uid_in_current_scope("ref$", SymbolFlags::FunctionScopedVariable)
+        .name;
+    let ref_ref = |ctx: &TraverseCtx<'a>| {
+        ctx.ast
+            .expression_identifier_reference(SPAN, ref_id.clone())
+    };
+    let is_function = ctx.ast.expression_binary(
+        SPAN,
+        ctx.ast
+            .expression_unary(SPAN, UnaryOperator::Typeof, ref_ref(ctx)),
+        BinaryOperator::StrictEquality,
+        ctx.ast.expression_string_literal(SPAN, "function"),
+    );
+    let call = call_expression(ref_ref(ctx), [param_ref(ctx)], ctx);
+    let expr = match target {
+        Some(target) => {
+            let assign = ctx.ast.expression_assignment(
+                SPAN,
+                AssignmentOperator::Assign,
+                target,
+                param_ref(ctx),
+            );
+            ctx.ast
+                .expression_conditional(SPAN, is_function, call, assign)
+        }
+        None => ctx
+            .ast
+            .expression_logical(SPAN, is_function, LogicalOperator::And, call),
+    };
+
+    let mut statements = ctx.ast.vec();
+    statements.push(ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+        SPAN,
+        ast::VariableDeclarationKind::Var,
+        ctx.ast.vec1(var_declarator(ref_id, value, ctx)),
+        false,
+    )));
+    statements.push(ctx.ast.statement_expression(SPAN, expr));
+    Some(object_method(
+        ast::PropertyKind::Init,
+        "ref",
+        ctx.ast.vec1(formal_parameter(param, ctx)),
+        statements,
+        ctx,
+    ))
+}
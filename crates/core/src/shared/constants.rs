@@ -0,0 +1,248 @@
+pub const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "keygen", "link", "menuitem",
+    "meta", "param", "source", "track", "wbr",
+];
+
+/// elements that always need an explicit closing tag, as the parser won't close them implicitly
+pub const ALWAYS_CLOSE: &[&str] = &[
+    "title", "style", "a", "strong", "small", "b", "u", "i", "em", "s", "code", "object", "table",
+    "button", "textarea", "select", "iframe", "script", "noscript", "template", "fieldset",
+];
+
+pub const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "acronym", "b", "bdi", "bdo", "big", "br", "button", "canvas", "cite", "code",
+    "data", "datalist", "del", "dfn", "em", "embed", "i", "iframe", "img", "input", "ins", "kbd",
+    "label", "map", "mark", "meter", "noscript", "object", "output", "picture", "progress", "q",
+    "ruby", "s", "samp", "script", "select", "slot", "small", "span", "strong", "sub", "sup",
+    "svg", "template", "textarea", "time", "u", "tt", "var", "video",
+];
+
+pub const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "li",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+pub const BOOLEANS: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "indeterminate",
+    "inert",
+    "ismap",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "seamless",
+    "selected",
+];
+
+/// attributes that are set as properties on the element
+pub fn is_property(name: &str) -> bool {
+    matches!(
+        name,
+        "className"
+            | "value"
+            | "readOnly"
+            | "formNoValidate"
+            | "isMap"
+            | "noModule"
+            | "playsInline"
+    ) || BOOLEANS.contains(&name)
+}
+
+/// properties that replace the children of the element
+pub const CHILD_PROPERTIES: &[&str] = &["innerHTML", "textContent", "innerText", "children"];
+
+/// get the attribute name for a JSX property name
+pub fn attribute_alias(name: &str) -> Option<&'static str> {
+    match name {
+        "className" => Some("class"),
+        "htmlFor" => Some("for"),
+        _ => None,
+    }
+}
+
+/// get the property name for an attribute name on the given (uppercased) tag
+pub fn property_alias(name: &str, tag_name: &str) -> Option<&'static str> {
+    match (name, tag_name) {
+        ("class", _) => Some("className"),
+        ("formnovalidate", "BUTTON" | "INPUT") => Some("formNoValidate"),
+        ("ismap", "IMG") => Some("isMap"),
+        ("nomodule", "SCRIPT") => Some("noModule"),
+        ("playsinline", "VIDEO") => Some("playsInline"),
+        ("readonly", "INPUT" | "TEXTAREA") => Some("readOnly"),
+        _ => None,
+    }
+}
+
+/// attribute namespaces that have a special meaning in JSX
+pub const RESERVED_NAMESPACES: &[&str] = &[
+    "class",
+    "on",
+    "oncapture",
+    "style",
+    "use",
+    "prop",
+    "attr",
+    "bool",
+];
+
+pub fn svg_namespace(prefix: &str) -> Option<&'static str> {
+    match prefix {
+        "xlink" => Some("http://www.w3.org/1999/xlink"),
+        "xml" => Some("http://www.w3.org/XML/1998/namespace"),
+        _ => None,
+    }
+}
+
+pub const SVG_ELEMENTS: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animate",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "color-profile",
+    "cursor",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "font",
+    "font-face",
+    "font-face-format",
+    "font-face-name",
+    "font-face-src",
+    "font-face-uri",
+    "foreignObject",
+    "g",
+    "glyph",
+    "glyphRef",
+    "hkern",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "missing-glyph",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "set",
+    "stop",
+    "svg",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "tref",
+    "tspan",
+    "use",
+    "view",
+    "vkern",
+];
+
+/// events that are handled by a single listener on the document
+pub const DELEGATED_EVENTS: &[&str] = &[
+    "beforeinput",
+    "click",
+    "dblclick",
+    "contextmenu",
+    "focusin",
+    "focusout",
+    "input",
+    "keydown",
+    "keyup",
+    "mousedown",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "pointerdown",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "touchend",
+    "touchmove",
+    "touchstart",
+];
@@ -0,0 +1,25 @@
+use oxc::{diagnostics::OxcDiagnostic, span::Span};
+
+pub fn invalid_event_namespace(namespace: &str, name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "The `{}:{}` attribute expects an event handler",
+        namespace, name
+    ))
+    .with_label(span)
+    .with_help(format!(
+        "Pass the handler as an expression, like `{}:{}={{handler}}`",
+        namespace, name
+    ))
+}
+
+pub fn invalid_event_handler_array(name: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error(format!(
+        "The `{}` attribute expects a `[handler, data]` array",
+        name
+    ))
+    .with_label(span)
+    .with_help(format!(
+        "Pass the handler and its optional data, like `{}={{[handler, data]}}`",
+        name
+    ))
+}
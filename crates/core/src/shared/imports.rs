@@ -0,0 +1,109 @@
+use oxc::{
+    ast::{ast, NONE},
+    semantic::SymbolFlags,
+    span::{Atom, SPAN},
+};
+use oxc_traverse::TraverseCtx;
+
+/// Collects the runtime helpers used by the generated code, so that each of them is imported only
+/// once per module.
+///
+/// The helpers aren't merged into one declaration per module: the babel plugin unshifts a
+/// declaration per helper to the top of the program, and the upstream fixtures expect that layout
+#[derive(Default)]
+pub struct ImportManager<'a> {
+    /// named imports already present in the source, reused instead of importing the helper again
+    existing: Vec<ImportedName<'a>>,
+    /// helpers that need to be imported, in the order they were first used
+    added: Vec<ImportedName<'a>>,
+    /// local names of namespace imports
+    namespaces: Vec<Atom<'a>>,
+}
+
+struct ImportedName<'a> {
+    module_name: String,
+    imported: String,
+    local: Atom<'a>,
+}
+
+impl<'a> ImportManager<'a> {
+    /// remember the named imports written by the user
+    pub fn collect_existing(&mut self, program: &ast::Program<'a>) {
+        for stmt in &program.body {
+            let ast::Statement::ImportDeclaration(decl) = stmt else {
+                continue;
+            };
+            if !decl.import_kind.is_value() {
+                continue;
+            }
+            let Some(specifiers) = &decl.specifiers else {
+                continue;
+            };
+            for specifier in specifiers {
+                match specifier {
+                    ast::ImportDeclarationSpecifier::ImportSpecifier(specifier)
+                        if specifier.import_kind.is_value() =>
+                    {
+                        self.existing.push(ImportedName {
+                            module_name: decl.source.value.to_string(),
+                            imported: specifier.imported.name().to_string(),
+                            local: specifier.local.name.clone(),
+                        });
+                    }
+                    ast::ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => {
+                        self.namespaces.push(specifier.local.name.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    pub fn is_namespace(&self, name: &str) -> bool {
+        self.namespaces.iter().any(|namespace| namespace == name)
+    }
+
+    /// get the local name of a helper, registering an import for it if necessary
+    pub fn add(&mut self, name: &str, module_name: &str, ctx: &mut TraverseCtx<'a>) -> Atom<'a> {
+        let found = self
+            .existing
+            .iter()
+            .chain(self.added.iter())
+            .find(|i| i.module_name == module_name && i.imported == name);
+        if let Some(import) = found {
+            return import.local.clone();
+        }
+
+        let local = ctx
+            .generate_uid_in_root_scope(&format!("${}", name), SymbolFlags::Import)
+            .name;
+        self.added.push(ImportedName {
+            module_name: module_name.to_string(),
+            imported: name.to_string(),
+            local: local.clone(),
+        });
+        local
+    }
+
+    /// insert one import declaration per registered helper at the top of the program, the most
+    /// recently registered one first like the babel plugin does
+    pub fn insert_into_program(&mut self, program: &mut ast::Program<'a>, ctx: &TraverseCtx<'a>) {
+        for (index, import) in self.added.drain(..).rev().enumerate() {
+            let specifier = ctx.ast.import_declaration_specifier_import_specifier(
+                SPAN,
+                ctx.ast
+                    .module_export_name_identifier_name(SPAN, import.imported.as_str()),
+                ctx.ast.binding_identifier(SPAN, import.local),
+                ast::ImportOrExportKind::Value,
+            );
+            let decl = ctx.ast.module_declaration_import_declaration(
+                SPAN,
+                Some(ctx.ast.vec1(specifier)),
+                ctx.ast.string_literal(SPAN, import.module_name.as_str()),
+                NONE,
+                ast::ImportOrExportKind::Value,
+            );
+            program.body.insert(index, ast::Statement::from(decl));
+        }
+    }
+}
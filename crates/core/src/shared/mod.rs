@@ -1,3 +1,7 @@
+pub mod component;
+pub mod constants;
+pub mod diagnostics;
+pub mod imports;
 pub mod this_to_self;
 pub mod transform;
 pub mod utils;
@@ -1,179 +1,532 @@
+use std::collections::HashSet;
+
 use html_escape::decode_html_entities;
 use oxc::{
     allocator::Vec as OxcVec,
     ast::ast::{self},
+    diagnostics::OxcDiagnostic,
     semantic::SymbolFlags,
-    span::{Atom, SPAN},
+    span::{Atom, GetSpan, GetSpanMut, Span, SPAN},
 };
 use oxc_traverse::{Traverse, TraverseCtx};
 
-use crate::{shared::utils::jsx_text_to_str, Config, OutputType};
+use crate::{
+    shared::{
+        diagnostics,
+        imports::ImportManager,
+        utils::{
+            arrow_function, escape_html, get_tag_name, is_component, is_meaningful_child,
+            jsx_text_to_str, static_expression_value, var_declarator, DynamicCheck,
+            FunctionBindings,
+        },
+    },
+    Config, OutputType,
+};
 
-pub struct JsxTransform {
-    config: Config,
+pub struct JsxTransform<'a> {
+    pub(crate) config: Config,
+    pub(crate) template_ctx: TemplateCreationCtx<'a>,
+    pub(crate) imports: ImportManager<'a>,
+    pub(crate) function_bindings: FunctionBindings,
+    /// names of the events that are delegated, in the order they were first used
+    pub(crate) events: Vec<String>,
+    /// start offsets of the expressions preceded by the static marker comment
+    pub(crate) static_markers: HashSet<u32>,
+    /// start offsets of the elements that are inserted into server side templates unescaped
+    pub(crate) wont_escape: HashSet<u32>,
+    /// the problems found in the source, which fail the transform once it is done
+    pub(crate) errors: Vec<OxcDiagnostic>,
+    /// the helpers creating the hoisted templates, whose calls are annotated as pure in the
+    /// generated code
+    pub(crate) pure_callees: Vec<String>,
 }
 
-impl JsxTransform {
+impl<'a> JsxTransform<'a> {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            template_ctx: TemplateCreationCtx::default(),
+            imports: ImportManager::default(),
+            function_bindings: FunctionBindings::default(),
+            events: Vec::new(),
+            static_markers: HashSet::new(),
+            wont_escape: HashSet::new(),
+            errors: Vec::new(),
+            pure_callees: Vec::new(),
+        }
+    }
+
+    /// get an identifier referencing the helper imported from the configured module
+    pub fn register_import_method(
+        &mut self,
+        name: &str,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let local = self.imports.add(name, &self.config.module_name, ctx);
+        ctx.ast.expression_identifier_reference(SPAN, local)
+    }
+
+    /// get an identifier referencing the helper of a renderer, which is imported from the module
+    /// configured for it in dynamic mode
+    pub fn register_renderer_method(
+        &mut self,
+        name: &str,
+        renderer: &str,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let module_name = self.config.renderer_module(renderer).to_string();
+        let local = self.imports.add(name, &module_name, ctx);
+        ctx.ast.expression_identifier_reference(SPAN, local)
     }
 }
 
 #[derive(Default)]
 pub struct TransformInfo {
-    top_level: bool,
-    skip_id: bool,
-    last_element: bool,
-    do_not_escape: bool,
+    pub(crate) top_level: bool,
+    pub(crate) skip_id: bool,
+    pub(crate) last_element: bool,
+    pub(crate) do_not_escape: bool,
+    pub(crate) fragment_child: bool,
+    pub(crate) component_child: bool,
+    /// whether the element is rendered inside of `NoHydration`, so its children need no markers
+    pub(crate) no_hydration: bool,
+    /// tags of the ancestors whose closing tags are kept in the template
+    pub(crate) to_be_closed: Option<HashSet<String>>,
 }
 
 pub struct TransformResult<'a> {
     pub id: Option<Atom<'a>>,
     pub template: Option<String>,
+    pub declarations: OxcVec<'a, ast::VariableDeclarator<'a>>,
     pub exprs: OxcVec<'a, ast::Expression<'a>>,
+    /// variables assigned by the expressions, which are declared where they are assigned
+    pub inline_declarations: Vec<Atom<'a>>,
     pub text: bool,
     pub skip_template: bool,
+    /// whether the expression of the result needs to be tracked
+    pub dynamic: bool,
+    pub tag_name: Option<String>,
+    pub to_be_closed: Option<HashSet<String>>,
+    /// attributes that are updated together in a single effect
+    pub dynamics: Vec<DynamicAttr<'a>>,
+    /// expressions that run after the effect of the dynamic attributes
+    pub post_exprs: OxcVec<'a, ast::Expression<'a>>,
+    /// whether the template is wrapped in an `<svg>` element
+    pub is_svg: bool,
+    /// whether the template has to be cloned with `document.importNode`
+    pub is_import_node: bool,
+    /// the strings of a server side template, with the template values interpolated between them
+    pub template_parts: Vec<String>,
+    pub template_values: OxcVec<'a, ast::Expression<'a>>,
+    /// whether the element is created by `ssrElement` because of its spread attributes
+    pub spread_element: bool,
+    /// the backend that created the element, which picks how its template is created in dynamic
+    /// mode
+    pub renderer: Option<OutputType>,
+    /// whether the server side template can be inserted without being escaped
+    pub wont_escape: bool,
+    /// whether the element has event handlers that need to be replayed after hydration
+    pub has_hydratable_event: bool,
+}
+
+/// an attribute whose value may change over time
+pub struct DynamicAttr<'a> {
+    pub elem: Atom<'a>,
+    pub key: String,
+    pub value: ast::Expression<'a>,
+    pub is_svg: bool,
+    pub is_ce: bool,
+    pub tag_name: String,
+    /// the attribute in the source, which the setter maps back to
+    pub span: Span,
 }
 
-impl<'a> Traverse<'a> for JsxTransform {
+impl<'a> TransformResult<'a> {
+    pub fn new(ctx: &TraverseCtx<'a>) -> Self {
+        Self {
+            id: None,
+            template: None,
+            declarations: ctx.ast.vec(),
+            exprs: ctx.ast.vec(),
+            inline_declarations: Vec::new(),
+            text: false,
+            skip_template: false,
+            dynamic: false,
+            tag_name: None,
+            to_be_closed: None,
+            dynamics: Vec::new(),
+            post_exprs: ctx.ast.vec(),
+            is_svg: false,
+            is_import_node: false,
+            template_parts: Vec::new(),
+            template_values: ctx.ast.vec(),
+            spread_element: false,
+            wont_escape: false,
+            renderer: None,
+            has_hydratable_event: false,
+        }
+    }
+}
+
+impl<'a> TransformResult<'a> {
+    /// turn the expressions into statements, the assignments of the inline declarations into
+    /// the declarations of their variables
+    pub fn expr_statements(
+        exprs: OxcVec<'a, ast::Expression<'a>>,
+        inline_declarations: &[Atom<'a>],
+        ctx: &TraverseCtx<'a>,
+    ) -> Vec<ast::Statement<'a>> {
+        exprs
+            .into_iter()
+            .map(|mut expr| {
+                if let ast::Expression::AssignmentExpression(assign) = &mut expr {
+                    let name = match &assign.left {
+                        ast::AssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                            Some(ident.name.clone())
+                        }
+                        _ => None,
+                    };
+                    if let Some(name) = name.filter(|name| inline_declarations.contains(name)) {
+                        let init = ctx.ast.move_expression(&mut assign.right);
+                        return ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                            SPAN,
+                            ast::VariableDeclarationKind::Var,
+                            ctx.ast.vec1(var_declarator(name, init, ctx)),
+                            false,
+                        ));
+                    }
+                }
+                ctx.ast.statement_expression(SPAN, expr)
+            })
+            .collect()
+    }
+}
+
+impl<'a> Traverse<'a> for JsxTransform<'a> {
+    fn enter_program(&mut self, node: &mut ast::Program<'a>, _ctx: &mut TraverseCtx<'a>) {
+        self.imports.collect_existing(node);
+        self.function_bindings.collect(node);
+        self.collect_static_markers(node);
+    }
+
     fn enter_expression(
         &mut self,
         node: &mut ast::Expression<'a>,
         ctx: &mut oxc_traverse::TraverseCtx<'a>,
     ) {
+        // the generated code maps back to the JSX it replaces
+        let span = node.span();
         match node {
             ast::Expression::JSXElement(_) => {
                 let ast::Expression::JSXElement(el) = ctx.ast.move_expression(node) else {
                     return;
                 };
                 let result = self.transform_node(
-                    &ctx.ast.jsx_child_from_jsx_element(el),
+                    &mut ctx.ast.jsx_child_from_jsx_element(el),
                     ctx,
-                    &Default::default(),
+                    &TransformInfo {
+                        top_level: true,
+                        last_element: true,
+                        ..Default::default()
+                    },
                 );
                 *node = result
-                    .map(|r| r.create_template(&self.config, ctx, false))
+                    .map(|r| {
+                        r.create_template(
+                            &self.config,
+                            ctx,
+                            &mut self.template_ctx,
+                            &mut self.imports,
+                            false,
+                        )
+                    })
                     .unwrap_or_else(|| ctx.ast.expression_null_literal(SPAN));
+                *node.span_mut() = span;
             }
             ast::Expression::JSXFragment(_) => {
                 let ast::Expression::JSXFragment(frag) = ctx.ast.move_expression(node) else {
                     return;
                 };
                 let result = self.transform_node(
-                    &ctx.ast.jsx_child_from_jsx_fragment(frag),
+                    &mut ctx.ast.jsx_child_from_jsx_fragment(frag),
                     ctx,
-                    &TransformInfo {
-                        top_level: true,
-                        last_element: true,
-                        ..Default::default()
-                    },
+                    &Default::default(),
                 );
                 *node = result
-                    .map(|r| r.create_template(&self.config, ctx, false))
+                    .map(|r| {
+                        r.create_template(
+                            &self.config,
+                            ctx,
+                            &mut self.template_ctx,
+                            &mut self.imports,
+                            false,
+                        )
+                    })
                     .unwrap_or_else(|| ctx.ast.expression_null_literal(SPAN));
+                *node.span_mut() = span;
             }
             _ => {}
         }
     }
+
+    fn exit_program(&mut self, node: &mut ast::Program<'a>, ctx: &mut TraverseCtx<'a>) {
+        // the helpers registered last are imported first, so `template` heads the imports
+        self.append_events_dom(node, ctx);
+        let templates = std::mem::take(&mut self.template_ctx.templates);
+        if !templates.is_empty() {
+            match self.config.generate {
+                OutputType::Dom | OutputType::Dynamic => {
+                    self.append_templates_dom(node, templates, ctx)
+                }
+                OutputType::Ssr => self.append_templates_ssr(node, templates, ctx),
+                // the elements of a custom renderer are created one by one
+                OutputType::Universal => {}
+            }
+        }
+        self.imports.insert_into_program(node, ctx);
+    }
 }
 
-impl<'a> JsxTransform {
+impl<'a> JsxTransform<'a> {
+    /// remember the expressions marked as static, dropping the marker comments from the output
+    fn collect_static_markers(&mut self, program: &mut ast::Program<'a>) {
+        let marker = self.config.static_marker.as_str();
+        if marker.is_empty() {
+            return;
+        }
+        let source = program.source_text;
+        program.comments.retain(|comment| {
+            let text = comment.span.source_text(source);
+            let text = text
+                .strip_prefix("/*")
+                .and_then(|text| text.strip_suffix("*/"))
+                .or_else(|| text.strip_prefix("//"))
+                .unwrap_or(text);
+            if text.trim() != marker {
+                return true;
+            }
+            let rest = &source[comment.span.end as usize..];
+            let rest = rest.strip_prefix("*/").unwrap_or(rest).trim_start();
+            self.static_markers
+                .insert((source.len() - rest.len()) as u32);
+            false
+        });
+    }
+
     pub fn transform_node(
-        &self,
-        node: &ast::JSXChild<'a>,
+        &mut self,
+        node: &mut ast::JSXChild<'a>,
         ctx: &mut TraverseCtx<'a>,
         info: &TransformInfo,
     ) -> Option<TransformResult<'a>> {
         match node {
-            ast::JSXChild::Element(el) => Some(self.transform_element(el, ctx)),
+            ast::JSXChild::Element(el) => Some(self.transform_element(el, ctx, info)),
             ast::JSXChild::Fragment(frag) => {
-                Some(self.transform_fragment_children(&frag.children, ctx, info))
+                Some(self.transform_fragment_children(&mut frag.children, ctx))
+            }
+            ast::JSXChild::Text(text) => {
+                let text = jsx_text_to_str(&text.value);
+                self.transform_text(text, ctx, info)
             }
-            ast::JSXChild::Text(text) => match jsx_text_to_str(&text.value) {
-                str if str.is_empty() => None,
-                str => Some(TransformResult {
-                    id: match info.skip_id {
-                        true => None,
-                        false => Some(
-                            ctx.generate_uid_in_current_scope(
-                                "el$",
-                                SymbolFlags::FunctionScopedVariable,
-                            )
-                            .name,
-                        ),
-                    },
-                    template: Some(str),
-                    text: true,
-                    exprs: ctx.ast.vec(),
-                    skip_template: false,
-                }),
-            },
             ast::JSXChild::ExpressionContainer(container) => {
-                // TODO
+                let expr = container.expression.as_expression_mut()?;
+                if !info.fragment_child && !info.component_child {
+                    if let Some(value) = static_expression_value(expr) {
+                        let text = match info.do_not_escape {
+                            true => value,
+                            false => escape_html(&value, false),
+                        };
+                        return self.transform_text(text, ctx, info);
+                    }
+                }
+
+                if !self.is_dynamic(
+                    expr,
+                    DynamicCheck {
+                        check_member: true,
+                        check_tags: info.component_child,
+                        native: !info.component_child,
+                        ..Default::default()
+                    },
+                    ctx,
+                ) {
+                    return Some(TransformResult {
+                        exprs: ctx.ast.vec1(ctx.ast.move_expression(expr)),
+                        ..TransformResult::new(ctx)
+                    });
+                }
+
+                let ssr = self.config.generate == OutputType::Ssr;
+                let expr = match expr {
+                    ast::Expression::LogicalExpression(_)
+                    | ast::Expression::ConditionalExpression(_)
+                        if self.config.wrap_conditionals
+                            && self.config.memo_wrapper.is_some()
+                            && !ssr =>
+                    {
+                        let expr = ctx.ast.move_expression(expr);
+                        self.transform_condition(
+                            expr,
+                            info.component_child || info.fragment_child,
+                            false,
+                            ctx,
+                        )
+                    }
+                    // a call without arguments can be passed as is, the callee gets called by the
+                    // runtime
+                    ast::Expression::CallExpression(call)
+                        if !info.component_child
+                            && (!ssr || info.fragment_child)
+                            && call.arguments.is_empty()
+                            && !call.callee.is_member_expression()
+                            && !matches!(call.callee, ast::Expression::CallExpression(_)) =>
+                    {
+                        ctx.ast.move_expression(&mut call.callee)
+                    }
+                    expr => {
+                        let expr = ctx.ast.move_expression(expr);
+                        arrow_function(ctx.ast.vec(), expr, ctx)
+                    }
+                };
                 Some(TransformResult {
-                    id: None,
-                    template: None,
-                    exprs: ctx.ast.vec(),
-                    text: false,
-                    skip_template: false,
+                    exprs: ctx.ast.vec1(expr),
+                    dynamic: true,
+                    ..TransformResult::new(ctx)
                 })
             }
             ast::JSXChild::Spread(spread) => {
-                // TODO
-                Some(TransformResult {
-                    id: None,
-                    template: None,
-                    exprs: ctx.ast.vec(),
-                    text: false,
-                    skip_template: false,
+                let expr = ctx.ast.move_expression(&mut spread.expression);
+                let check = DynamicCheck {
+                    check_member: true,
+                    native: !info.component_child,
+                    ..Default::default()
+                };
+                Some(match self.is_dynamic(&expr, check, ctx) {
+                    true => TransformResult {
+                        exprs: ctx.ast.vec1(arrow_function(ctx.ast.vec(), expr, ctx)),
+                        dynamic: true,
+                        ..TransformResult::new(ctx)
+                    },
+                    false => TransformResult {
+                        exprs: ctx.ast.vec1(expr),
+                        ..TransformResult::new(ctx)
+                    },
                 })
             }
         }
     }
 
-    pub fn transform_element(
+    fn transform_text(
         &self,
-        el: &ast::JSXElement<'a>,
+        text: String,
         ctx: &mut TraverseCtx<'a>,
+        info: &TransformInfo,
+    ) -> Option<TransformResult<'a>> {
+        if text.is_empty() {
+            return None;
+        }
+        Some(TransformResult {
+            // server side templates are strings, so there are no nodes to reference
+            id: match info.skip_id || self.config.generate == OutputType::Ssr {
+                true => None,
+                false => Some(
+                    ctx.generate_uid_in_current_scope("el$", SymbolFlags::FunctionScopedVariable)
+                        .name,
+                ),
+            },
+            template: Some(text),
+            text: true,
+            ..TransformResult::new(ctx)
+        })
+    }
+
+    /// report the namespaced attributes whose value can't be used by their namespace
+    fn validate_attributes(&mut self, el: &ast::JSXElement<'a>) {
+        for item in &el.opening_element.attributes {
+            let ast::JSXAttributeItem::Attribute(attr) = item else {
+                continue;
+            };
+            let ast::JSXAttributeName::NamespacedName(name) = &attr.name else {
+                continue;
+            };
+            let namespace = name.namespace.name.as_str();
+            if matches!(namespace, "on" | "oncapture")
+                && !matches!(
+                    attr.value,
+                    Some(ast::JSXAttributeValue::ExpressionContainer(_))
+                )
+            {
+                self.errors.push(diagnostics::invalid_event_namespace(
+                    namespace,
+                    &name.property.name,
+                    attr.span,
+                ));
+            }
+        }
+    }
+
+    pub fn transform_element(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+        info: &TransformInfo,
     ) -> TransformResult<'a> {
+        let tag_name = get_tag_name(el);
+        if is_component(&tag_name) {
+            return self.transform_component(el, ctx);
+        }
+        self.validate_attributes(el);
         match self.config.generate {
-            OutputType::Dom => self.transform_element_dom(el, ctx),
+            OutputType::Dom => self.transform_element_dom(el, ctx, info),
+            OutputType::Ssr => self.transform_element_ssr(el, ctx, info),
+            OutputType::Universal => self.transform_element_universal(el, ctx, info),
+            OutputType::Dynamic => match self.config.element_renderer(&tag_name) {
+                Some("dom") => self.transform_element_dom(el, ctx, info),
+                _ => self.transform_element_universal(el, ctx, info),
+            },
         }
     }
 
     pub fn transform_fragment_children(
-        &self,
-        children: &OxcVec<'a, ast::JSXChild<'a>>,
+        &mut self,
+        children: &mut OxcVec<'a, ast::JSXChild<'a>>,
         ctx: &mut TraverseCtx<'a>,
-        info: &TransformInfo,
     ) -> TransformResult<'a> {
-        let filtered = children.iter().filter(|child| match child {
-            ast::JSXChild::ExpressionContainer(container) => {
-                !matches!(container.expression, ast::JSXExpression::EmptyExpression(_))
-            }
-            // TODO: this doesn't 100% match with the original behavior
-            // (https://github.com/ryansolid/dom-expressions/blob/388985beae617521fe7daff06759e9d704b852fa/packages/babel-plugin-jsx-dom-expressions/src/shared/utils.js#L196)
+        let info = TransformInfo {
+            top_level: true,
+            last_element: true,
+            fragment_child: true,
+            ..Default::default()
+        };
+        // TODO: this doesn't 100% match with the original behavior
+        // (https://github.com/ryansolid/dom-expressions/blob/388985beae617521fe7daff06759e9d704b852fa/packages/babel-plugin-jsx-dom-expressions/src/shared/utils.js#L196)
+        let filtered = children.iter_mut().filter(|child| match child {
             ast::JSXChild::Text(text) => !text.value.trim().is_empty(),
-            _ => true,
+            child => is_meaningful_child(child),
         });
-        let child_nodes = ctx
-            .ast
-            .vec_from_iter(filtered.filter_map(|child| match child {
+        let mut child_nodes = ctx.ast.vec();
+        for child in filtered {
+            match child {
                 ast::JSXChild::Text(text) => {
                     let v = jsx_text_to_str(&text.value);
                     let v = decode_html_entities(&v);
-                    match v.is_empty() {
-                        true => None,
-                        false => Some(ctx.ast.expression_string_literal(text.span, v)),
+                    if !v.is_empty() {
+                        child_nodes.push(ctx.ast.expression_string_literal(text.span, v));
                     }
                 }
                 child => {
-                    let child_result = self.transform_node(child, ctx, info);
-                    child_result
-                        .as_ref()
-                        .map(|r| r.create_template(&self.config, ctx, true))
+                    if let Some(r) = self.transform_node(child, ctx, &info) {
+                        child_nodes.push(r.create_template(
+                            &self.config,
+                            ctx,
+                            &mut self.template_ctx,
+                            &mut self.imports,
+                            true,
+                        ));
+                    }
                 }
-            }));
+            }
+        }
         TransformResult {
             exprs: match child_nodes.len() > 1 {
                 true => ctx.ast.vec1(
@@ -189,31 +542,35 @@ impl<'a> JsxTransform {
                 ),
                 false => child_nodes,
             },
-            id: None,
-            template: None,
-            text: false,
-            skip_template: false,
+            ..TransformResult::new(ctx)
         }
     }
 }
 
 impl<'a> TransformResult<'a> {
-    fn create_template(
-        &self,
+    pub(crate) fn create_template(
+        self,
         config: &Config,
         ctx: &mut oxc_traverse::TraverseCtx<'a>,
+        creation_ctx: &mut TemplateCreationCtx<'a>,
+        imports: &mut ImportManager<'a>,
         wrap: bool,
     ) -> ast::Expression<'a> {
-        let mut creation_ctx = TemplateCreationCtx {
-            templates: Vec::new(),
-        };
-
         match config.generate {
-            OutputType::Dom => self.create_template_dom(config, ctx, &mut creation_ctx, wrap),
+            OutputType::Dom => self.create_template_dom(config, ctx, creation_ctx, imports, wrap),
+            OutputType::Ssr => self.create_template_ssr(config, ctx, creation_ctx, imports),
+            OutputType::Universal => self.create_template_universal(config, ctx, imports, wrap),
+            OutputType::Dynamic => match self.renderer {
+                Some(OutputType::Dom) => {
+                    self.create_template_dom(config, ctx, creation_ctx, imports, wrap)
+                }
+                _ => self.create_template_universal(config, ctx, imports, wrap),
+            },
         }
     }
 }
 
+#[derive(Default)]
 pub struct TemplateCreationCtx<'a> {
     pub templates: Vec<Template<'a>>,
 }
@@ -222,11 +579,16 @@ pub struct Template<'a> {
     pub id: Atom<'a>,
     pub template: String,
     pub renderer: OutputType,
+    pub is_svg: bool,
+    pub is_import_node: bool,
+    /// the strings of a server side template
+    pub parts: Vec<String>,
 }
 
 #[cfg(test)]
 mod transform_tests {
     use super::*;
+    use crate::shared::utils::static_truthiness;
     use oxc::{allocator::Allocator, parser::Parser, semantic::SemanticBuilder, span::SourceType};
 
     struct TestCase {
@@ -276,6 +638,30 @@ mod transform_tests {
                 expected_exprs_len: 0,
                 expected_text: false,
             },
+            /* solidJS client side rendering result
+                import { template as _$template } from "solid-js/web";
+                var _tmpl$ = /*#__PURE__*/_$template(`<div>100000000000000000000`); // <-
+                const foo = _tmpl$();
+            */
+            TestCase {
+                source: r#"<div>{1e20}</div>"#,
+                expected_id: None,
+                expected_template: Some(r#"<div>100000000000000000000"#.to_string()),
+                expected_exprs_len: 0,
+                expected_text: false,
+            },
+            /* solidJS client side rendering result
+                import { template as _$template } from "solid-js/web";
+                var _tmpl$ = /*#__PURE__*/_$template(`<div>0.5`); // <-
+                const foo = _tmpl$();
+            */
+            TestCase {
+                source: r#"<div>{1 / 2}</div>"#,
+                expected_id: None,
+                expected_template: Some(r#"<div>0.5"#.to_string()),
+                expected_exprs_len: 0,
+                expected_text: false,
+            },
         ];
 
         for case in test_cases {
@@ -283,23 +669,28 @@ mod transform_tests {
             let source_type = SourceType::jsx();
 
             let parse_result = Parser::new(&allocator, case.source, source_type).parse();
-            let program = parse_result.program;
+            let mut program = parse_result.program;
 
             let semantic_result = SemanticBuilder::new(case.source)
                 .with_excess_capacity(2.0)
                 .build(&program);
             let (symbols, scopes) = semantic_result.semantic.into_symbol_table_and_scope_tree();
 
-            if let ast::Statement::ExpressionStatement(expr_stmt) = &program.body[0] {
-                if let ast::Expression::JSXElement(jsx_element) = &expr_stmt.expression {
+            if let ast::Statement::ExpressionStatement(expr_stmt) = &mut program.body[0] {
+                if let ast::Expression::JSXElement(jsx_element) = &mut expr_stmt.expression {
                     let mut ctx = TraverseCtx::new(scopes, symbols, &allocator);
                     let config = Config {
                         generate: OutputType::Dom,
                         ..Default::default()
                     };
-                    let transform = JsxTransform::new(config);
+                    let mut transform = JsxTransform::new(config);
+                    let info = TransformInfo {
+                        top_level: true,
+                        last_element: true,
+                        ..Default::default()
+                    };
 
-                    let result = transform.transform_element(jsx_element, &mut ctx);
+                    let result = transform.transform_element(jsx_element, &mut ctx, &info);
 
                     assert_eq!(
                         result.id, case.expected_id,
@@ -330,4 +721,56 @@ mod transform_tests {
             }
         }
     }
+
+    #[test]
+    fn test_static_expression_value() {
+        let cases = [
+            ("1e20", Some("100000000000000000000")),
+            ("1 / 2", Some("0.5")),
+            ("0.000001", Some("0.000001")),
+            // JavaScript writes it as `5e-7`
+            ("5e-7", None),
+            ("1e21", None),
+            ("1 / 0", None),
+            ("'a' + 1", Some("a1")),
+        ];
+        for (source, expected) in cases {
+            let allocator = Allocator::default();
+            let expr = Parser::new(&allocator, source, SourceType::mjs())
+                .parse_expression()
+                .unwrap();
+            assert_eq!(
+                static_expression_value(&expr).as_deref(),
+                expected,
+                "Failed for source: {}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_static_truthiness() {
+        let cases = [
+            ("\"0\"", Some(true)),
+            ("\"\"", Some(false)),
+            ("0", Some(false)),
+            ("-0", Some(false)),
+            ("0 / 0", Some(false)),
+            ("5e-7", Some(true)),
+            ("undefined", Some(false)),
+            ("props.value", None),
+        ];
+        for (source, expected) in cases {
+            let allocator = Allocator::default();
+            let expr = Parser::new(&allocator, source, SourceType::mjs())
+                .parse_expression()
+                .unwrap();
+            assert_eq!(
+                static_truthiness(&expr),
+                expected,
+                "Failed for source: {}",
+                source
+            );
+        }
+    }
 }
@@ -1,4 +1,19 @@
-use oxc::span::Atom;
+use std::collections::{HashMap, HashSet};
+
+use oxc::{
+    allocator::Vec as OxcVec,
+    ast::{
+        ast,
+        visit::{walk, Visit},
+        NONE,
+    },
+    semantic::{ReferenceId, ScopeFlags, SymbolFlags, SymbolId},
+    span::{Atom, GetSpan, SPAN},
+    syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::{shared::transform::JsxTransform, OutputType};
 
 pub fn jsx_text_to_str(t: &Atom) -> String {
     let mut buf = String::new();
@@ -25,3 +40,968 @@ pub fn jsx_text_to_str(t: &Atom) -> String {
     }
     buf
 }
+
+/// whether the child should take part in the output, skipping empty expressions and
+/// whitespace-only text that spans multiple lines
+pub fn is_meaningful_child(child: &ast::JSXChild) -> bool {
+    match child {
+        ast::JSXChild::ExpressionContainer(container) => {
+            !matches!(container.expression, ast::JSXExpression::EmptyExpression(_))
+        }
+        ast::JSXChild::Text(text) => {
+            !(text.value.starts_with(['\r', '\n']) && text.value.trim().is_empty())
+        }
+        _ => true,
+    }
+}
+
+/// escape a string so that it can be used as the raw content of a template literal
+pub fn escape_template(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '{' | '\\' | '`') {
+            buf.push('\\');
+        }
+        buf.push(c);
+    }
+    buf
+}
+
+/// wrap the statements in an immediately invoked arrow function
+pub fn iife<'a>(
+    statements: OxcVec<'a, ast::Statement<'a>>,
+    ctx: &mut TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    let arrow_fn = arrow_function_block(ctx.ast.vec(), statements, ctx);
+    ctx.ast.expression_call(
+        SPAN,
+        ctx.ast.expression_parenthesized(SPAN, arrow_fn),
+        NONE,
+        ctx.ast.vec(),
+        false,
+    )
+}
+
+/// create an arrow function with a block body
+pub fn arrow_function_block<'a>(
+    params: OxcVec<'a, ast::FormalParameter<'a>>,
+    statements: OxcVec<'a, ast::Statement<'a>>,
+    ctx: &mut TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    let arrow_fn = ctx.ast.arrow_function_expression(
+        SPAN,
+        false,
+        false,
+        NONE,
+        ctx.ast.formal_parameters(
+            SPAN,
+            ast::FormalParameterKind::ArrowFormalParameters,
+            params,
+            NONE,
+        ),
+        NONE,
+        ctx.ast.function_body(SPAN, ctx.ast.vec(), statements),
+    );
+    arrow_fn
+        .scope_id
+        .set(Some(ctx.create_child_scope_of_current(ScopeFlags::Arrow)));
+    ctx.ast.expression_from_arrow_function(arrow_fn)
+}
+
+/// create a parameter binding the name
+pub fn formal_parameter<'a>(name: Atom<'a>, ctx: &TraverseCtx<'a>) -> ast::FormalParameter<'a> {
+    ctx.ast.formal_parameter(
+        SPAN,
+        ctx.ast.vec(),
+        ctx.ast.binding_pattern(
+            ctx.ast.binding_pattern_kind_binding_identifier(SPAN, name),
+            NONE,
+            false,
+        ),
+        None,
+        false,
+        false,
+    )
+}
+
+/// escape the characters that would otherwise be parsed as markup, either in text content or in a
+/// double quoted attribute value
+pub fn escape_html(s: &str, attr: bool) -> String {
+    let delim = match attr {
+        true => '"',
+        false => '<',
+    };
+    let mut buf = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => buf.push_str("&amp;"),
+            c if c == delim && attr => buf.push_str("&quot;"),
+            c if c == delim => buf.push_str("&lt;"),
+            c => buf.push(c),
+        }
+    }
+    buf
+}
+
+/// collapse the line breaks and indentation of a multiline attribute value into single spaces
+pub fn trim_whitespace(text: &str) -> String {
+    let text = text.replace('\r', "");
+    let text = match text.contains('\n') {
+        true => text
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| match i {
+                0 => line,
+                _ => line.trim_start(),
+            })
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+        false => text,
+    };
+    let mut buf = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c.is_whitespace() {
+            true if buf.ends_with(' ') => {}
+            true => buf.push(' '),
+            false => buf.push(c),
+        }
+    }
+    buf
+}
+
+/// count the children that produce output, treating whitespace-only text as meaningful only when
+/// it doesn't contain line breaks
+pub fn check_length(children: &[ast::JSXChild]) -> bool {
+    children
+        .iter()
+        .filter(|child| match child {
+            ast::JSXChild::ExpressionContainer(container) => {
+                !matches!(container.expression, ast::JSXExpression::EmptyExpression(_))
+            }
+            ast::JSXChild::Text(text) => {
+                !text.value.trim().is_empty() || text.value.chars().all(|c| c == ' ')
+            }
+            _ => true,
+        })
+        .count()
+        > 1
+}
+
+enum StaticValue {
+    String(String),
+    Number(f64),
+}
+
+impl StaticValue {
+    /// the string of the value, or none for a number that isn't formatted like JavaScript does
+    fn into_string(self) -> Option<String> {
+        match self {
+            StaticValue::String(s) => Some(s),
+            StaticValue::Number(n) => number_to_string(n),
+        }
+    }
+}
+
+/// format a number like `Number.prototype.toString`, which only agrees with rust for the safe
+/// integers and for the other numbers JavaScript doesn't write with an exponent
+fn number_to_string(n: f64) -> Option<String> {
+    if n.fract() == 0.0 && n.abs() < 2f64.powi(53) {
+        // `-0` is written as `0`
+        Some(format!("{}", n as i64))
+    } else if n.is_finite() && (1e-6..1e21).contains(&n.abs()) {
+        Some(format!("{}", n))
+    } else {
+        None
+    }
+}
+
+fn evaluate(expr: &ast::Expression) -> Option<StaticValue> {
+    match expr {
+        ast::Expression::StringLiteral(lit) => Some(StaticValue::String(lit.value.to_string())),
+        ast::Expression::NumericLiteral(lit) => Some(StaticValue::Number(lit.value)),
+        ast::Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => lit
+            .quasis
+            .first()
+            .and_then(|quasi| quasi.value.cooked.as_ref())
+            .map(|cooked| StaticValue::String(cooked.to_string())),
+        ast::Expression::ParenthesizedExpression(expr) => evaluate(&expr.expression),
+        ast::Expression::UnaryExpression(expr) if expr.operator == UnaryOperator::UnaryNegation => {
+            match evaluate(&expr.argument)? {
+                StaticValue::Number(n) => Some(StaticValue::Number(-n)),
+                StaticValue::String(_) => None,
+            }
+        }
+        ast::Expression::BinaryExpression(expr) => {
+            let left = evaluate(&expr.left)?;
+            let right = evaluate(&expr.right)?;
+            match (expr.operator, left, right) {
+                (BinaryOperator::Addition, StaticValue::Number(l), StaticValue::Number(r)) => {
+                    Some(StaticValue::Number(l + r))
+                }
+                (BinaryOperator::Addition, l, r) => {
+                    Some(StaticValue::String(l.into_string()? + &r.into_string()?))
+                }
+                (BinaryOperator::Subtraction, StaticValue::Number(l), StaticValue::Number(r)) => {
+                    Some(StaticValue::Number(l - r))
+                }
+                (
+                    BinaryOperator::Multiplication,
+                    StaticValue::Number(l),
+                    StaticValue::Number(r),
+                ) => Some(StaticValue::Number(l * r)),
+                (BinaryOperator::Division, StaticValue::Number(l), StaticValue::Number(r)) => {
+                    Some(StaticValue::Number(l / r))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// get the string value of an expression that can be evaluated at compile time, so that it can be
+/// inlined into the template
+pub fn static_expression_value(expr: &ast::Expression) -> Option<String> {
+    evaluate(expr).and_then(StaticValue::into_string)
+}
+
+/// get the truthiness of an expression that can be evaluated at compile time
+pub fn static_truthiness(expr: &ast::Expression) -> Option<bool> {
+    match expr {
+        ast::Expression::BooleanLiteral(lit) => Some(lit.value),
+        ast::Expression::NullLiteral(_) => Some(false),
+        ast::Expression::Identifier(ident) if ident.name == "undefined" => Some(false),
+        expr => evaluate(expr).map(|value| match value {
+            StaticValue::String(s) => !s.is_empty(),
+            StaticValue::Number(n) => n != 0.0 && !n.is_nan(),
+        }),
+    }
+}
+
+/// create an arrow function that returns the expression
+pub fn arrow_function<'a>(
+    params: OxcVec<'a, ast::FormalParameter<'a>>,
+    body: ast::Expression<'a>,
+    ctx: &mut TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    let arrow_fn = ctx.ast.arrow_function_expression(
+        SPAN,
+        true,
+        false,
+        NONE,
+        ctx.ast.formal_parameters(
+            SPAN,
+            ast::FormalParameterKind::ArrowFormalParameters,
+            params,
+            NONE,
+        ),
+        NONE,
+        ctx.ast.function_body(
+            SPAN,
+            ctx.ast.vec(),
+            ctx.ast.vec1(ctx.ast.statement_expression(SPAN, body)),
+        ),
+    );
+    arrow_fn
+        .scope_id
+        .set(Some(ctx.create_child_scope_of_current(ScopeFlags::Arrow)));
+    ctx.ast.expression_from_arrow_function(arrow_fn)
+}
+
+/// create a `var` declarator binding the name to the value
+pub fn var_declarator<'a>(
+    name: Atom<'a>,
+    init: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::VariableDeclarator<'a> {
+    ctx.ast.variable_declarator(
+        SPAN,
+        ast::VariableDeclarationKind::Var,
+        ctx.ast.binding_pattern(
+            ctx.ast.binding_pattern_kind_binding_identifier(SPAN, name),
+            NONE,
+            false,
+        ),
+        Some(init),
+        false,
+    )
+}
+
+/// create a non-computed member expression
+pub fn static_member<'a>(
+    object: ast::Expression<'a>,
+    property: &str,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    ast::Expression::from(ctx.ast.member_expression_static(
+        SPAN,
+        object,
+        ctx.ast.identifier_name(SPAN, ctx.ast.atom(property)),
+        false,
+    ))
+}
+
+/// create an assignment of the value to the variable
+pub fn assign_identifier<'a>(
+    name: Atom<'a>,
+    value: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    ctx.ast.expression_assignment(
+        SPAN,
+        AssignmentOperator::Assign,
+        ast::AssignmentTarget::from(ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(
+            ctx.ast.alloc(ctx.ast.identifier_reference(SPAN, name)),
+        )),
+        value,
+    )
+}
+
+/// create an assignment to a non-computed member of the object
+pub fn assign_member<'a>(
+    object: ast::Expression<'a>,
+    property: &str,
+    value: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    let member = ctx.ast.member_expression_static(
+        SPAN,
+        object,
+        ctx.ast.identifier_name(SPAN, ctx.ast.atom(property)),
+        false,
+    );
+    ctx.ast.expression_assignment(
+        SPAN,
+        AssignmentOperator::Assign,
+        ast::AssignmentTarget::from(ast::SimpleAssignmentTarget::from(member)),
+        value,
+    )
+}
+
+/// turn an identifier or a member expression into the target of an assignment
+pub fn assignment_target<'a>(expr: ast::Expression<'a>) -> Option<ast::AssignmentTarget<'a>> {
+    match expr {
+        ast::Expression::Identifier(ident) => Some(ast::AssignmentTarget::from(
+            ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(ident),
+        )),
+        expr if expr.is_member_expression() => Some(ast::AssignmentTarget::from(
+            ast::SimpleAssignmentTarget::from(expr.into_member_expression()),
+        )),
+        _ => None,
+    }
+}
+
+const RESERVED_WORDS: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// whether the name can be used as an identifier
+pub fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        && !RESERVED_WORDS.contains(&name)
+}
+
+/// create the key of an object property, using a string literal for names that aren't valid
+/// identifiers
+pub fn property_key<'a>(name: &str, ctx: &TraverseCtx<'a>) -> ast::PropertyKey<'a> {
+    match is_valid_identifier(name) {
+        true => ast::PropertyKey::StaticIdentifier(
+            ctx.ast
+                .alloc(ctx.ast.identifier_name(SPAN, ctx.ast.atom(name))),
+        ),
+        false => ast::PropertyKey::StringLiteral(
+            ctx.ast
+                .alloc(ctx.ast.string_literal(SPAN, ctx.ast.atom(name))),
+        ),
+    }
+}
+
+/// create a non-computed, non-shorthand object property
+pub fn object_property<'a>(
+    key: &str,
+    value: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::ObjectPropertyKind<'a> {
+    ctx.ast.object_property_kind_object_property(
+        SPAN,
+        ast::PropertyKind::Init,
+        property_key(key, ctx),
+        value,
+        None,
+        false,
+        false,
+        false,
+    )
+}
+
+/// create a getter or method property with the given body, e.g. `get key() { ... }`
+pub fn object_method<'a>(
+    kind: ast::PropertyKind,
+    key: &str,
+    params: OxcVec<'a, ast::FormalParameter<'a>>,
+    statements: OxcVec<'a, ast::Statement<'a>>,
+    ctx: &mut TraverseCtx<'a>,
+) -> ast::ObjectPropertyKind<'a> {
+    let function = ctx.ast.function(
+        SPAN,
+        ast::FunctionType::FunctionExpression,
+        None,
+        false,
+        false,
+        false,
+        NONE,
+        NONE,
+        ctx.ast.formal_parameters(
+            SPAN,
+            ast::FormalParameterKind::FormalParameter,
+            params,
+            NONE,
+        ),
+        NONE,
+        Some(ctx.ast.function_body(SPAN, ctx.ast.vec(), statements)),
+    );
+    function.scope_id.set(Some(
+        ctx.create_child_scope_of_current(ScopeFlags::Function),
+    ));
+    ctx.ast.object_property_kind_object_property(
+        SPAN,
+        kind,
+        property_key(key, ctx),
+        ctx.ast.expression_from_function(function),
+        None,
+        kind == ast::PropertyKind::Init,
+        false,
+        !is_valid_identifier(key),
+    )
+}
+
+/// get the name of the tag, joining member expressions with dots
+pub fn get_tag_name(el: &ast::JSXElement) -> String {
+    fn member_name(member: &ast::JSXMemberExpression) -> String {
+        let object = match &member.object {
+            ast::JSXMemberExpressionObject::IdentifierReference(ident) => ident.name.to_string(),
+            ast::JSXMemberExpressionObject::MemberExpression(member) => member_name(member),
+            ast::JSXMemberExpressionObject::ThisExpression(_) => "this".to_string(),
+        };
+        format!("{}.{}", object, member.property.name)
+    }
+
+    match &el.opening_element.name {
+        ast::JSXElementName::Identifier(ident) => ident.name.to_string(),
+        ast::JSXElementName::IdentifierReference(ident) => ident.name.to_string(),
+        ast::JSXElementName::NamespacedName(name) => {
+            format!("{}:{}", name.namespace.name, name.property.name)
+        }
+        ast::JSXElementName::MemberExpression(member) => member_name(member),
+        ast::JSXElementName::ThisExpression(_) => "this".to_string(),
+    }
+}
+
+/// remove the `$ServerOnly` flag from the element, returning whether it was set
+pub fn take_server_only(el: &mut ast::JSXElement) -> bool {
+    let attributes = &mut el.opening_element.attributes;
+    let len = attributes.len();
+    attributes.retain(|item| {
+        !matches!(item, ast::JSXAttributeItem::Attribute(attr)
+            if matches!(&attr.name, ast::JSXAttributeName::Identifier(ident)
+                if ident.name == "$ServerOnly"))
+    });
+    attributes.len() != len
+}
+
+/// whether the tag refers to a component instead of a native element
+pub fn is_component(tag_name: &str) -> bool {
+    tag_name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_uppercase() || !c.is_ascii_alphabetic())
+        || tag_name.contains('.')
+}
+
+/// create a call expression with the given arguments
+pub fn call_expression<'a>(
+    callee: ast::Expression<'a>,
+    args: impl IntoIterator<Item = ast::Expression<'a>>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    ctx.ast.expression_call(
+        SPAN,
+        callee,
+        NONE,
+        ctx.ast
+            .vec_from_iter(args.into_iter().map(|arg| ctx.ast.argument_expression(arg))),
+        false,
+    )
+}
+
+/// create a template literal interpolating the values between the quasis, which has to hold one
+/// more quasi than there are values
+pub fn template_literal<'a>(
+    quasis: Vec<String>,
+    values: Vec<ast::Expression<'a>>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    let tail = quasis.len() - 1;
+    let quasis = ctx
+        .ast
+        .vec_from_iter(quasis.into_iter().enumerate().map(|(i, quasi)| {
+            ctx.ast.template_element(
+                SPAN,
+                i == tail,
+                ast::TemplateElementValue {
+                    raw: ctx.ast.atom(&escape_template(&quasi)),
+                    cooked: Some(ctx.ast.atom(&quasi)),
+                },
+            )
+        }));
+    ctx.ast
+        .expression_template_literal(SPAN, quasis, ctx.ast.vec_from_iter(values))
+}
+
+/// Bindings that are known to hold a function, so that event handlers referencing them can be
+/// attached without a wrapper
+#[derive(Default)]
+pub struct FunctionBindings {
+    functions: HashSet<SymbolId>,
+    /// bindings initialized with another identifier
+    aliases: HashMap<SymbolId, ReferenceId>,
+}
+
+impl FunctionBindings {
+    pub fn collect(&mut self, program: &ast::Program) {
+        self.visit_program(program);
+    }
+
+    /// whether the identifier refers to a function declaration or a variable initialized with one
+    pub fn is_function(&self, name: &str, ctx: &TraverseCtx) -> bool {
+        let mut symbol_id = ctx.scopes().find_binding(ctx.current_scope_id(), name);
+        // aliases can't be circular, but the chain is limited anyway to be safe
+        for _ in 0..16 {
+            let Some(id) = symbol_id else {
+                return false;
+            };
+            if self.functions.contains(&id) {
+                return true;
+            }
+            symbol_id = self
+                .aliases
+                .get(&id)
+                .and_then(|reference_id| ctx.symbols().get_reference(*reference_id).symbol_id());
+        }
+        false
+    }
+}
+
+impl<'a> Visit<'a> for FunctionBindings {
+    fn visit_function(&mut self, it: &ast::Function<'a>, flags: ScopeFlags) {
+        if it.r#type == ast::FunctionType::FunctionDeclaration {
+            if let Some(symbol_id) = it.id.as_ref().and_then(|id| id.symbol_id.get()) {
+                self.functions.insert(symbol_id);
+            }
+        }
+        walk::walk_function(self, it, flags);
+    }
+
+    fn visit_variable_declarator(&mut self, it: &ast::VariableDeclarator<'a>) {
+        if let ast::BindingPatternKind::BindingIdentifier(id) = &it.id.kind {
+            if let Some(symbol_id) = id.symbol_id.get() {
+                match &it.init {
+                    Some(init) if init.is_function() => {
+                        self.functions.insert(symbol_id);
+                    }
+                    Some(ast::Expression::Identifier(ident)) => {
+                        if let Some(reference_id) = ident.reference_id.get() {
+                            self.aliases.insert(symbol_id, reference_id);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        walk::walk_variable_declarator(self, it);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DynamicCheck {
+    pub check_member: bool,
+    pub check_tags: bool,
+    pub check_call_expressions: bool,
+    pub native: bool,
+}
+
+impl Default for DynamicCheck {
+    fn default() -> Self {
+        Self {
+            check_member: false,
+            check_tags: false,
+            check_call_expressions: true,
+            native: false,
+        }
+    }
+}
+
+struct DynamicVisitor {
+    check: DynamicCheck,
+    dynamic: bool,
+}
+
+impl<'a> Visit<'a> for DynamicVisitor {
+    fn visit_expression(&mut self, it: &ast::Expression<'a>) {
+        if !self.dynamic {
+            walk::walk_expression(self, it);
+        }
+    }
+
+    // function bodies run later, so they don't make the expression itself reactive
+    fn visit_function(&mut self, _it: &ast::Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _it: &ast::ArrowFunctionExpression<'a>) {}
+
+    fn visit_object_property(&mut self, it: &ast::ObjectProperty<'a>) {
+        if matches!(it.value, ast::Expression::FunctionExpression(_)) {
+            if it.computed {
+                self.visit_property_key(&it.key);
+            }
+            return;
+        }
+        walk::walk_object_property(self, it);
+    }
+
+    fn visit_call_expression(&mut self, it: &ast::CallExpression<'a>) {
+        match self.check.check_call_expressions {
+            true => self.dynamic = true,
+            false => walk::walk_call_expression(self, it),
+        }
+    }
+
+    fn visit_member_expression(&mut self, it: &ast::MemberExpression<'a>) {
+        match self.check.check_member {
+            true => self.dynamic = true,
+            false => walk::walk_member_expression(self, it),
+        }
+    }
+
+    fn visit_spread_element(&mut self, it: &ast::SpreadElement<'a>) {
+        match self.check.check_member {
+            true => self.dynamic = true,
+            false => walk::walk_spread_element(self, it),
+        }
+    }
+
+    fn visit_binary_expression(&mut self, it: &ast::BinaryExpression<'a>) {
+        match self.check.check_member && it.operator == BinaryOperator::In {
+            true => self.dynamic = true,
+            false => walk::walk_binary_expression(self, it),
+        }
+    }
+
+    fn visit_jsx_element(&mut self, _it: &ast::JSXElement<'a>) {
+        if self.check.check_tags {
+            self.dynamic = true;
+        }
+    }
+
+    fn visit_jsx_fragment(&mut self, it: &ast::JSXFragment<'a>) {
+        if self.check.check_tags && !it.children.is_empty() {
+            self.dynamic = true;
+        }
+    }
+}
+
+impl<'a> JsxTransform<'a> {
+    /// whether the expression may change over time and needs to be wrapped to be tracked
+    pub fn is_dynamic(
+        &self,
+        expr: &ast::Expression<'a>,
+        check: DynamicCheck,
+        ctx: &TraverseCtx<'a>,
+    ) -> bool {
+        if expr.is_function() || self.static_markers.contains(&expr.span().start) {
+            return false;
+        }
+        // a server side template is rendered once, so only components can see updates
+        let check = match self.config.generate == OutputType::Ssr && check.native {
+            true => DynamicCheck {
+                check_member: false,
+                check_call_expressions: false,
+                ..check
+            },
+            false => check,
+        };
+        if let ast::Expression::TaggedTemplateExpression(_) = expr {
+            return check.check_call_expressions;
+        }
+
+        // property access on namespace imports is not considered dynamic
+        if let Some(member) = expr.as_member_expression() {
+            if let ast::Expression::Identifier(object) = member.object() {
+                let is_static_property = match member {
+                    ast::MemberExpression::ComputedMemberExpression(computed) => {
+                        !self.is_dynamic(&computed.expression, check, ctx)
+                    }
+                    _ => true,
+                };
+                if is_static_property && self.is_namespace_import(&object.name, ctx) {
+                    return false;
+                }
+            }
+        }
+
+        let mut visitor = DynamicVisitor {
+            check,
+            dynamic: false,
+        };
+        visitor.visit_expression(expr);
+        visitor.dynamic
+    }
+
+    /// prepare the argument of a spread attribute to be passed to `mergeProps`, returning
+    /// whether it is dynamic and has to be read lazily
+    pub fn transform_spread_argument(
+        &self,
+        argument: ast::Expression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> (ast::Expression<'a>, bool) {
+        let check = DynamicCheck {
+            check_member: true,
+            ..Default::default()
+        };
+        if !self.is_dynamic(&argument, check, ctx) {
+            return (argument, false);
+        }
+        let argument = match argument {
+            // a call without arguments can be passed as is, the callee gets called when the
+            // props are read
+            ast::Expression::CallExpression(mut call)
+                if call.arguments.is_empty()
+                    && !call.callee.is_member_expression()
+                    && !matches!(call.callee, ast::Expression::CallExpression(_)) =>
+            {
+                ctx.ast.move_expression(&mut call.callee)
+            }
+            argument => arrow_function(ctx.ast.vec(), argument, ctx),
+        };
+        (argument, true)
+    }
+
+    /// memoize the test of a conditional or `&&` expression whose branches are expensive to
+    /// evaluate, so that they only run again when the result of the test changes
+    ///
+    /// with `inline`, the memo is created in place instead of in a variable declared by an
+    /// immediately called arrow function, `deep` returns the expression itself instead of a
+    /// function returning it
+    pub fn transform_condition(
+        &mut self,
+        mut expr: ast::Expression<'a>,
+        inline: bool,
+        deep: bool,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let check_tags = DynamicCheck {
+            check_tags: true,
+            ..Default::default()
+        };
+        let check_member = DynamicCheck {
+            check_member: true,
+            ..Default::default()
+        };
+        let mut declaration = None;
+        match &mut expr {
+            ast::Expression::ConditionalExpression(conditional)
+                if self.is_dynamic(&conditional.consequent, check_tags, ctx)
+                    || self.is_dynamic(&conditional.alternate, check_tags, ctx) =>
+            {
+                if self.is_dynamic(&conditional.test, check_member, ctx) {
+                    let test = ctx.ast.move_expression(&mut conditional.test);
+                    conditional.test = self.condition_test(test, inline, &mut declaration, ctx);
+                    for branch in [&mut conditional.consequent, &mut conditional.alternate] {
+                        if matches!(
+                            branch,
+                            ast::Expression::ConditionalExpression(_)
+                                | ast::Expression::LogicalExpression(_)
+                        ) {
+                            let value = ctx.ast.move_expression(branch);
+                            *branch = self.transform_condition(value, inline, true, ctx);
+                        }
+                    }
+                }
+            }
+            ast::Expression::LogicalExpression(logical) => {
+                // the condition of `a && b || c` is on the left
+                let mut next: &mut ast::LogicalExpression<'a> = logical;
+                while next.operator != LogicalOperator::And {
+                    match &mut next.left {
+                        ast::Expression::LogicalExpression(left) => next = left,
+                        _ => break,
+                    }
+                }
+                if next.operator == LogicalOperator::And
+                    && self.is_dynamic(&next.right, check_tags, ctx)
+                    && self.is_dynamic(&next.left, check_member, ctx)
+                {
+                    let test = ctx.ast.move_expression(&mut next.left);
+                    next.left = self.condition_test(test, inline, &mut declaration, ctx);
+                }
+            }
+            _ => {}
+        }
+
+        match declaration {
+            Some((id, cond)) => {
+                let cond = arrow_function(ctx.ast.vec(), cond, ctx);
+                let init = match self.config.memo_wrapper.clone() {
+                    Some(memo_wrapper) => {
+                        let memo = self.register_import_method(&memo_wrapper, ctx);
+                        call_expression(memo, [cond], ctx)
+                    }
+                    None => cond,
+                };
+                let mut statements = ctx.ast.vec();
+                statements.push(ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                    SPAN,
+                    ast::VariableDeclarationKind::Var,
+                    ctx.ast.vec1(var_declarator(id, init, ctx)),
+                    false,
+                )));
+                let getter = arrow_function(ctx.ast.vec(), expr, ctx);
+                statements.push(ctx.ast.statement_return(SPAN, Some(getter)));
+                iife(statements, ctx)
+            }
+            None if deep => expr,
+            None => arrow_function(ctx.ast.vec(), expr, ctx),
+        }
+    }
+
+    /// replace the test of a condition with a call to its memo
+    fn condition_test(
+        &mut self,
+        test: ast::Expression<'a>,
+        inline: bool,
+        declaration: &mut Option<(Atom<'a>, ast::Expression<'a>)>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let cond = match test {
+            ast::Expression::BinaryExpression(_) => test,
+            test => {
+                let not = ctx
+                    .ast
+                    .expression_unary(SPAN, UnaryOperator::LogicalNot, test);
+                ctx.ast
+                    .expression_unary(SPAN, UnaryOperator::LogicalNot, not)
+            }
+        };
+        let callee = match (inline, self.config.memo_wrapper.clone()) {
+            (true, Some(memo_wrapper)) => {
+                let memo = self.register_import_method(&memo_wrapper, ctx);
+                let cond = arrow_function(ctx.ast.vec(), cond, ctx);
+                call_expression(memo, [cond], ctx)
+            }
+            _ => {
+                let id = ctx
+                    .generate_uid_in_current_scope("c$", SymbolFlags::FunctionScopedVariable)
+                    .name;
+                *declaration = Some((id.clone(), cond));
+                ctx.ast.expression_identifier_reference(SPAN, id)
+            }
+        };
+        call_expression(callee, [], ctx)
+    }
+
+    fn is_namespace_import(&self, name: &str, ctx: &TraverseCtx<'a>) -> bool {
+        ctx.scopes()
+            .find_binding(ctx.current_scope_id(), name)
+            .is_some_and(|symbol_id| {
+                ctx.symbols()
+                    .get_flags(symbol_id)
+                    .contains(SymbolFlags::Import)
+                    && self.imports.is_namespace(name)
+            })
+    }
+}
+
+/// remove the typescript wrappers (`!`, `as`, `satisfies`) around an expression
+pub fn strip_ts_wrappers<'a>(
+    mut expr: ast::Expression<'a>,
+    ctx: &TraverseCtx<'a>,
+) -> ast::Expression<'a> {
+    loop {
+        expr = match expr {
+            ast::Expression::TSNonNullExpression(mut expr) => {
+                ctx.ast.move_expression(&mut expr.expression)
+            }
+            ast::Expression::TSAsExpression(mut expr) => {
+                ctx.ast.move_expression(&mut expr.expression)
+            }
+            ast::Expression::TSSatisfiesExpression(mut expr) => {
+                ctx.ast.move_expression(&mut expr.expression)
+            }
+            expr => return expr,
+        };
+    }
+}
+
+/// whether the expression is an identifier bound to a `const` declaration
+pub fn is_const_binding(expr: &ast::Expression, ctx: &TraverseCtx) -> bool {
+    match expr {
+        ast::Expression::Identifier(ident) => ctx
+            .scopes()
+            .find_binding(ctx.current_scope_id(), &ident.name)
+            .is_some_and(|symbol_id| {
+                ctx.symbols()
+                    .get_flags(symbol_id)
+                    .contains(SymbolFlags::ConstVariable)
+            }),
+        _ => false,
+    }
+}
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+
+use oxc::sourcemap::{SourceMap, SourceMapBuilder};
+
+/// chain the map of the output to the map of its input, so that the output maps back to the
+/// original source the input was generated from
+pub fn chain(map: &SourceMap, input_map: &SourceMap) -> SourceMap {
+    let lookup_table = input_map.generate_lookup_table();
+    let mut builder = SourceMapBuilder::default();
+    let mut source_ids = HashMap::new();
+    let mut name_ids = HashMap::new();
+
+    for token in map.get_tokens() {
+        // positions without a counterpart in the original source can't be mapped, the lookup
+        // falls back to the last token of a previous line
+        let Some(original) = input_map
+            .lookup_token(&lookup_table, token.get_src_line(), token.get_src_col())
+            .filter(|original| original.get_dst_line() == token.get_src_line())
+        else {
+            continue;
+        };
+        let source_id = original.get_source_id().and_then(|id| {
+            let source = input_map.get_source(id)?.to_string();
+            let content = input_map
+                .get_source_content(id)
+                .map(|content| content.to_string())
+                .unwrap_or_default();
+            Some(
+                *source_ids
+                    .entry(id)
+                    .or_insert_with(|| builder.set_source_and_content(&source, &content)),
+            )
+        });
+        // names of the original source take precedence over the ones of the input
+        let name = match original.get_name_id() {
+            Some(id) => input_map.get_name(id),
+            None => token.get_name_id().and_then(|id| map.get_name(id)),
+        };
+        let name_id = name.map(|name| {
+            let name = name.to_string();
+            *name_ids
+                .entry(name.clone())
+                .or_insert_with(|| builder.add_name(&name))
+        });
+        builder.add_token(
+            token.get_dst_line(),
+            token.get_dst_col(),
+            original.get_src_line(),
+            original.get_src_col(),
+            source_id,
+            name_id,
+        );
+    }
+    builder.into_sourcemap()
+}
+
+/// shift the generated columns of the map by the length of the text inserted at the positions,
+/// given as lines and columns of the generated code
+pub fn shift_columns(map: &SourceMap, insertions: &[(u32, u32)], len: u32) -> SourceMap {
+    let mut builder = SourceMapBuilder::default();
+    let source_ids = map
+        .get_sources()
+        .enumerate()
+        .map(|(id, source)| {
+            let content = map
+                .get_source_content(id as u32)
+                .map(|content| content.to_string())
+                .unwrap_or_default();
+            builder.set_source_and_content(source, &content)
+        })
+        .collect::<Vec<_>>();
+    let name_ids = map
+        .get_names()
+        .map(|name| builder.add_name(name))
+        .collect::<Vec<_>>();
+
+    for token in map.get_tokens() {
+        let shift = insertions
+            .iter()
+            .filter(|(line, column)| {
+                *line == token.get_dst_line() && *column <= token.get_dst_col()
+            })
+            .count() as u32
+            * len;
+        builder.add_token(
+            token.get_dst_line(),
+            token.get_dst_col() + shift,
+            token.get_src_line(),
+            token.get_src_col(),
+            token.get_source_id().map(|id| source_ids[id as usize]),
+            token.get_name_id().map(|id| name_ids[id as usize]),
+        );
+    }
+    builder.into_sourcemap()
+}
@@ -0,0 +1,1050 @@
+use html_escape::decode_html_entities;
+use oxc::{
+    allocator::CloneIn,
+    ast::ast,
+    span::SPAN,
+    syntax::operator::{BinaryOperator, LogicalOperator},
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::shared::{
+    constants::{
+        attribute_alias, BOOLEANS, CHILD_PROPERTIES, RESERVED_NAMESPACES, SVG_ELEMENTS,
+        VOID_ELEMENTS,
+    },
+    transform::{JsxTransform, TransformInfo, TransformResult},
+    utils::{
+        arrow_function, call_expression, check_length, escape_html, get_tag_name, is_component,
+        is_meaningful_child, object_method, object_property, static_expression_value,
+        take_server_only, template_literal, trim_whitespace, DynamicCheck,
+    },
+};
+
+/// an attribute of a native element, with string values turned into string literals
+struct Attribute<'a> {
+    key: String,
+    value: Option<ast::Expression<'a>>,
+    /// whether the value was written as an expression container
+    container: bool,
+}
+
+enum AttributeItem<'a> {
+    Attribute(Attribute<'a>),
+    Spread(ast::Expression<'a>),
+}
+
+impl<'a> JsxTransform<'a> {
+    pub fn transform_element_ssr(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+        info: &TransformInfo,
+    ) -> TransformResult<'a> {
+        let tag_name = get_tag_name(el);
+        let hydration_key = info.top_level && self.config.hydratable;
+        // the head isn't hydrated, so it is rendered without hydration keys
+        if hydration_key && tag_name == "head" {
+            let child = self.transform_element_ssr(el, ctx, &TransformInfo::default());
+            let child = child.create_template(
+                &self.config,
+                ctx,
+                &mut self.template_ctx,
+                &mut self.imports,
+                false,
+            );
+            return TransformResult {
+                exprs: ctx.ast.vec1(self.no_hydration_ssr(child, ctx)),
+                tag_name: Some(tag_name),
+                ..TransformResult::new(ctx)
+            };
+        }
+
+        let raw_text = tag_name == "script" || tag_name == "style";
+        // the server renders the element like any other, the flag is only read by the client
+        take_server_only(el);
+        let attributes = collect_attributes(el, ctx);
+        let has_spread = attributes
+            .iter()
+            .any(|attr| matches!(attr, AttributeItem::Spread(_)));
+        let attributes = self.normalize_attributes_ssr(attributes, ctx);
+        if has_spread {
+            return self.create_element_ssr(el, &tag_name, attributes, raw_text, info, ctx);
+        }
+
+        let mut results = TransformResult {
+            template_parts: vec![format!("<{}", tag_name)],
+            tag_name: Some(tag_name.clone()),
+            wont_escape: self.wont_escape.contains(&el.span.start),
+            ..TransformResult::new(ctx)
+        };
+        if hydration_key {
+            let ssr_hydration_key = self.register_import_method("ssrHydrationKey", ctx);
+            results.template_parts.push(String::new());
+            results
+                .template_values
+                .push(call_expression(ssr_hydration_key, [], ctx));
+        }
+        let do_not_escape = self.transform_attributes_ssr(el, attributes, &mut results, ctx);
+        append_to_template(&mut results.template_parts, ">");
+        if !VOID_ELEMENTS.contains(&tag_name.as_str()) {
+            let markers =
+                self.config.hydratable && !info.no_hydration && check_length(&el.children);
+            self.transform_children_ssr(el, &mut results, do_not_escape || raw_text, markers, ctx);
+            append_to_template(&mut results.template_parts, &format!("</{}>", tag_name));
+        }
+        results
+    }
+
+    /// render the content without hydration keys, for the parts of the page the client doesn't
+    /// hydrate
+    fn no_hydration_ssr(
+        &mut self,
+        children: ast::Expression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let create_component = self.register_import_method("createComponent", ctx);
+        let no_hydration = self.register_import_method("NoHydration", ctx);
+        let getter = object_method(
+            ast::PropertyKind::Get,
+            "children",
+            ctx.ast.vec(),
+            ctx.ast.vec1(ctx.ast.statement_return(SPAN, Some(children))),
+            ctx,
+        );
+        let props = ctx.ast.expression_object(SPAN, ctx.ast.vec1(getter), None);
+        call_expression(create_component, [no_hydration, props], ctx)
+    }
+
+    /// Create an element with spread attributes through `ssrElement`, which renders the
+    /// attributes at runtime
+    fn create_element_ssr(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        tag_name: &str,
+        mut attributes: Vec<AttributeItem<'a>>,
+        do_not_escape: bool,
+        info: &TransformInfo,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> TransformResult<'a> {
+        let has_children = !el.children.is_empty();
+        let mut child_nodes = Vec::new();
+        for child in el
+            .children
+            .iter_mut()
+            .filter(|child| is_meaningful_child(child))
+        {
+            match child {
+                ast::JSXChild::Text(text) => {
+                    let value = trim_whitespace(&text.value);
+                    let value = decode_html_entities(&value);
+                    if !value.is_empty() {
+                        child_nodes.push(ctx.ast.expression_string_literal(text.span, value));
+                    }
+                }
+                child => {
+                    let Some(mut result) = self.transform_node(child, ctx, &Default::default())
+                    else {
+                        continue;
+                    };
+                    if !result.exprs.is_empty() && !do_not_escape && !result.spread_element {
+                        let expr = result.exprs.remove(0);
+                        let expr = self.escape_expression_ssr(expr, false, false, ctx);
+                        result.exprs.insert(0, expr);
+                    }
+                    child_nodes.push(result.create_template(
+                        &self.config,
+                        ctx,
+                        &mut self.template_ctx,
+                        &mut self.imports,
+                        true,
+                    ));
+                }
+            }
+        }
+
+        let props = match attributes.pop() {
+            // a lone spread is passed as it is
+            Some(AttributeItem::Spread(argument)) if attributes.is_empty() => argument,
+            last => {
+                attributes.extend(last);
+                let mut props = Vec::new();
+                let mut running_object = ctx.ast.vec();
+                let mut dynamic_spread = false;
+                for item in attributes {
+                    let Attribute {
+                        key,
+                        value,
+                        container,
+                    } = match item {
+                        AttributeItem::Spread(argument) => {
+                            if !running_object.is_empty() {
+                                props.push(ctx.ast.expression_object(
+                                    SPAN,
+                                    std::mem::replace(&mut running_object, ctx.ast.vec()),
+                                    None,
+                                ));
+                            }
+                            let (argument, dynamic) = self.transform_spread_argument(argument, ctx);
+                            dynamic_spread |= dynamic;
+                            props.push(argument);
+                            continue;
+                        }
+                        AttributeItem::Attribute(attr) => attr,
+                    };
+                    if (has_children && key == "children")
+                        || key == "ref"
+                        || key.starts_with("use:")
+                        || key.starts_with("prop:")
+                        || key.starts_with("on")
+                    {
+                        continue;
+                    }
+                    let value =
+                        value.unwrap_or_else(|| ctx.ast.expression_boolean_literal(SPAN, true));
+                    let check = DynamicCheck {
+                        check_member: true,
+                        check_tags: true,
+                        ..Default::default()
+                    };
+                    running_object.push(match container && self.is_dynamic(&value, check, ctx) {
+                        true => {
+                            let statements =
+                                ctx.ast.vec1(ctx.ast.statement_return(SPAN, Some(value)));
+                            object_method(
+                                ast::PropertyKind::Get,
+                                &key,
+                                ctx.ast.vec(),
+                                statements,
+                                ctx,
+                            )
+                        }
+                        false => object_property(&key, value, ctx),
+                    });
+                }
+                if !running_object.is_empty() || props.is_empty() {
+                    props.push(ctx.ast.expression_object(SPAN, running_object, None));
+                }
+                match props.len() > 1 || dynamic_spread {
+                    true => {
+                        let merge_props = self.register_import_method("mergeProps", ctx);
+                        call_expression(merge_props, props, ctx)
+                    }
+                    false => props.remove(0),
+                }
+            }
+        };
+
+        let children = match child_nodes.len() {
+            0 => None,
+            1 => Some(child_nodes.remove(0)),
+            _ => Some(
+                ctx.ast.expression_array(
+                    SPAN,
+                    ctx.ast.vec_from_iter(
+                        child_nodes
+                            .into_iter()
+                            .map(|expr| ctx.ast.array_expression_element_expression(expr)),
+                    ),
+                    None,
+                ),
+            ),
+        };
+        let children = match children {
+            None => ctx.ast.expression_identifier_reference(SPAN, "undefined"),
+            // the children are rendered after the hydration key of the element is claimed
+            Some(children) if self.config.hydratable => {
+                arrow_function(ctx.ast.vec(), children, ctx)
+            }
+            Some(children) => children,
+        };
+        let ssr_element = self.register_import_method("ssrElement", ctx);
+        let tag = ctx
+            .ast
+            .expression_string_literal(SPAN, ctx.ast.atom(tag_name));
+        let call = call_expression(
+            ssr_element,
+            [
+                tag,
+                props,
+                children,
+                ctx.ast
+                    .expression_boolean_literal(SPAN, info.top_level && self.config.hydratable),
+            ],
+            ctx,
+        );
+        TransformResult {
+            exprs: ctx.ast.vec1(call),
+            tag_name: Some(tag_name.to_string()),
+            spread_element: true,
+            ..TransformResult::new(ctx)
+        }
+    }
+
+    /// Merge the `class:` and `style:` attributes into `classList` and `style` objects, and the
+    /// class attributes into a single `class`
+    fn normalize_attributes_ssr(
+        &mut self,
+        attributes: Vec<AttributeItem<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Vec<AttributeItem<'a>> {
+        // the babel plugin removes the merged namespaced attributes by their position in the
+        // source, so each attribute keeps it
+        let mut attributes = attributes.into_iter().enumerate().collect::<Vec<_>>();
+        let mut removed = Vec::new();
+        let style_attributes = namespaced_attributes(&attributes, "style:");
+        let class_attributes = namespaced_attributes(&attributes, "class:");
+        transform_to_object(
+            &mut attributes,
+            &mut removed,
+            &class_attributes,
+            "classList",
+            ctx,
+        );
+
+        let indices = attributes
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, item))| {
+                matches!(item, AttributeItem::Attribute(attr)
+                    if matches!(attr.key.as_str(), "class" | "className" | "classList"))
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if indices.len() > 1 {
+            let mut quasis = vec![String::new()];
+            let mut values = Vec::new();
+            for (i, index) in indices.iter().enumerate() {
+                let (_, AttributeItem::Attribute(attr)) = &mut attributes[*index] else {
+                    continue;
+                };
+                let separator = match i == indices.len() - 1 {
+                    true => "",
+                    false => " ",
+                };
+                let value = attr.value.take();
+                if !attr.container {
+                    let text = value
+                        .as_ref()
+                        .and_then(static_expression_value)
+                        .unwrap_or_default();
+                    if let Some(quasi) = quasis.last_mut() {
+                        quasi.push_str(&text);
+                        quasi.push_str(separator);
+                    }
+                    continue;
+                }
+                let Some(value) = value else {
+                    continue;
+                };
+                let value = match value {
+                    ast::Expression::ObjectExpression(mut obj)
+                        if attr.key == "classList" && !has_spread(&obj) =>
+                    {
+                        self.transform_classlist_object(&mut obj, &mut values, &mut quasis, ctx);
+                        if let Some(quasi) = quasis.last_mut() {
+                            quasi.push_str(separator);
+                        }
+                        continue;
+                    }
+                    value if attr.key == "classList" => {
+                        let ssr_class_list = self.register_import_method("ssrClassList", ctx);
+                        call_expression(ssr_class_list, [value], ctx)
+                    }
+                    value => value,
+                };
+                values.push(ctx.ast.expression_logical(
+                    SPAN,
+                    value,
+                    LogicalOperator::Or,
+                    ctx.ast.expression_string_literal(SPAN, ""),
+                ));
+                quasis.push(separator.to_string());
+            }
+
+            let first = indices[0];
+            if let (_, AttributeItem::Attribute(attr)) = &mut attributes[first] {
+                attr.key = "class".to_string();
+                attr.value = Some(template_literal(quasis, values, ctx));
+                attr.container = true;
+            }
+            attributes = attributes
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| *index == first || !indices.contains(index))
+                .map(|(_, item)| item)
+                .collect();
+        }
+
+        transform_to_object(
+            &mut attributes,
+            &mut removed,
+            &style_attributes,
+            "style",
+            ctx,
+        );
+        attributes.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Add the attributes to the template, returning whether the children are raw html that
+    /// must not be escaped
+    fn transform_attributes_ssr(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        attributes: Vec<AttributeItem<'a>>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> bool {
+        let tag_name = results.tag_name.clone().unwrap_or_default();
+        let is_svg = SVG_ELEMENTS.contains(&tag_name.as_str());
+        let has_children = !el.children.is_empty();
+        let mut do_not_escape = false;
+        let mut children = None;
+
+        for item in attributes {
+            let AttributeItem::Attribute(Attribute {
+                key,
+                value,
+                container,
+            }) = item
+            else {
+                continue;
+            };
+            let namespace = key
+                .split_once(':')
+                .map(|(namespace, _)| namespace)
+                .filter(|namespace| RESERVED_NAMESPACES.contains(namespace));
+            let is_child_property = CHILD_PROPERTIES.contains(&key.as_str());
+            let is_expression = namespace.is_some()
+                || is_child_property
+                || (container
+                    && !matches!(
+                        value,
+                        Some(
+                            ast::Expression::StringLiteral(_)
+                                | ast::Expression::NumericLiteral(_)
+                                | ast::Expression::BooleanLiteral(_)
+                        )
+                    ));
+
+            if is_expression {
+                if key == "ref"
+                    || key.starts_with("use:")
+                    || key.starts_with("prop:")
+                    || key.starts_with("on")
+                {
+                    continue;
+                }
+                if is_child_property {
+                    if key == "innerHTML" {
+                        do_not_escape = true;
+                    }
+                    children = value;
+                    continue;
+                }
+                let value = value.unwrap_or_else(|| ctx.ast.expression_boolean_literal(SPAN, true));
+                let key = key.strip_prefix("attr:").unwrap_or(&key);
+                if BOOLEANS.contains(&key) {
+                    let ssr_attribute = self.register_import_method("ssrAttribute", ctx);
+                    let name = ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(key));
+                    let toggle = ctx.ast.expression_boolean_literal(SPAN, true);
+                    results.template_parts.push(String::new());
+                    results.template_values.push(call_expression(
+                        ssr_attribute,
+                        [name, value, toggle],
+                        ctx,
+                    ));
+                    continue;
+                }
+                // style and class values are rendered by helpers that escape them already
+                let (key, value, escaped) = match key {
+                    "style" => ("style", self.transform_style_ssr(value, ctx), false),
+                    "classList" => ("class", self.transform_class_list_ssr(value, ctx), false),
+                    key => (
+                        key,
+                        self.escape_expression_ssr(value, true, false, ctx),
+                        true,
+                    ),
+                };
+                if !escaped
+                    || value.is_literal()
+                    || matches!(value, ast::Expression::TemplateLiteral(_))
+                {
+                    append_to_template(&mut results.template_parts, &format!(" {}=\"", key));
+                    results.template_parts.push("\"".to_string());
+                    results.template_values.push(value);
+                } else {
+                    self.set_attr_ssr(results, key, value, is_svg, ctx);
+                }
+                continue;
+            }
+
+            let key = attribute_alias(&key).map(String::from).unwrap_or(key);
+            let is_boolean = BOOLEANS.contains(&key.as_str());
+            // a boolean attribute that is known to be off is left out
+            let off = match &value {
+                Some(ast::Expression::BooleanLiteral(lit)) => !lit.value,
+                Some(ast::Expression::NumericLiteral(lit)) => {
+                    lit.value == 0.0 || lit.value.is_nan()
+                }
+                _ => false,
+            };
+            if is_boolean && off {
+                continue;
+            }
+            let key = match is_svg {
+                true => key,
+                false => key.to_lowercase(),
+            };
+            append_to_template(&mut results.template_parts, &format!(" {}", key));
+            let Some(value) = value else {
+                continue;
+            };
+            let mut text = match (&value, is_boolean) {
+                (_, true) => String::new(),
+                (ast::Expression::BooleanLiteral(lit), _) => lit.value.to_string(),
+                (value, _) => static_expression_value(value).unwrap_or_default(),
+            };
+            if key == "style" || key == "class" {
+                text = trim_whitespace(&text);
+                if key == "style" {
+                    text = text.replace("; ", ";").replace(": ", ":");
+                }
+            }
+            append_to_template(
+                &mut results.template_parts,
+                &format!("=\"{}\"", escape_html(&text, true)),
+            );
+        }
+
+        // `innerHTML` and friends render the content of the element
+        if let (false, Some(children)) = (has_children, children) {
+            el.children.push(
+                ctx.ast
+                    .jsx_child_expression_container(SPAN, ast::JSXExpression::from(children)),
+            );
+        }
+        do_not_escape
+    }
+
+    /// render an attribute through `ssrAttribute`, which leaves it out when its value is nullish
+    fn set_attr_ssr(
+        &mut self,
+        results: &mut TransformResult<'a>,
+        name: &str,
+        value: ast::Expression<'a>,
+        is_svg: bool,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let name = match name.split_once(':') {
+            Some((namespace, name)) if RESERVED_NAMESPACES.contains(&namespace) => name,
+            _ => name,
+        };
+        let name = attribute_alias(name).unwrap_or(name);
+        let name = match is_svg {
+            true => name.to_string(),
+            false => name.to_lowercase(),
+        };
+        let ssr_attribute = self.register_import_method("ssrAttribute", ctx);
+        let attr = call_expression(
+            ssr_attribute,
+            [
+                ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(&name)),
+                value,
+                ctx.ast.expression_boolean_literal(SPAN, false),
+            ],
+            ctx,
+        );
+        // attributes following each other share a single template value
+        let last_is_empty = results
+            .template_parts
+            .last()
+            .is_some_and(|part| part.is_empty());
+        match (last_is_empty, results.template_values.pop()) {
+            (true, Some(last)) => {
+                results.template_values.push(ctx.ast.expression_binary(
+                    SPAN,
+                    last,
+                    BinaryOperator::Addition,
+                    attr,
+                ));
+            }
+            (_, last) => {
+                results.template_values.extend(last);
+                results.template_parts.push(String::new());
+                results.template_values.push(attr);
+            }
+        }
+    }
+
+    /// turn a `style` object into the concatenation of its properties, or render it with
+    /// `ssrStyle`
+    fn transform_style_ssr(
+        &mut self,
+        value: ast::Expression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let obj = match value {
+            ast::Expression::ObjectExpression(obj) if !has_spread(&obj) => obj,
+            value => {
+                let ssr_style = self.register_import_method("ssrStyle", ctx);
+                return call_expression(ssr_style, [value], ctx);
+            }
+        };
+        let mut style: Option<ast::Expression<'a>> = None;
+        for (i, prop) in obj.unbox().properties.into_iter().enumerate() {
+            let ast::ObjectPropertyKind::ObjectProperty(mut prop) = prop else {
+                continue;
+            };
+            let name = prop.key.static_name().unwrap_or_default();
+            let name = format!("{}{}:", if i > 0 { ";" } else { "" }, name);
+            let value = ctx.ast.move_expression(&mut prop.value);
+            let part = ctx.ast.expression_binary(
+                SPAN,
+                ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(&name)),
+                BinaryOperator::Addition,
+                self.escape_expression_ssr(value, true, true, ctx),
+            );
+            style = Some(match style {
+                Some(style) => {
+                    ctx.ast
+                        .expression_binary(SPAN, style, BinaryOperator::Addition, part)
+                }
+                None => part,
+            });
+        }
+        style.unwrap_or_else(|| ctx.ast.expression_string_literal(SPAN, ""))
+    }
+
+    /// turn a `classList` object into the class names it toggles, or render it with
+    /// `ssrClassList`
+    fn transform_class_list_ssr(
+        &mut self,
+        value: ast::Expression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let mut obj = match value {
+            ast::Expression::ObjectExpression(obj) if !has_spread(&obj) => obj,
+            value => {
+                let ssr_class_list = self.register_import_method("ssrClassList", ctx);
+                return call_expression(ssr_class_list, [value], ctx);
+            }
+        };
+        let mut values = Vec::new();
+        let mut quasis = vec![String::new()];
+        self.transform_classlist_object(&mut obj, &mut values, &mut quasis, ctx);
+        match values.len() {
+            0 => ctx
+                .ast
+                .expression_string_literal(SPAN, ctx.ast.atom(&quasis[0])),
+            1 if quasis.iter().all(|quasi| quasi.is_empty()) => values.remove(0),
+            _ => template_literal(quasis, values, ctx),
+        }
+    }
+
+    /// Append the classes of a `classList` object to the quasis and values of a template literal,
+    /// inlining the ones that are always on
+    fn transform_classlist_object(
+        &mut self,
+        obj: &mut ast::ObjectExpression<'a>,
+        values: &mut Vec<ast::Expression<'a>>,
+        quasis: &mut Vec<String>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let len = obj.properties.len();
+        for (i, prop) in obj.properties.iter_mut().enumerate() {
+            let ast::ObjectPropertyKind::ObjectProperty(prop) = prop else {
+                continue;
+            };
+            let separator = match i == len - 1 {
+                true => "",
+                false => " ",
+            };
+            let name = match prop.computed {
+                true => None,
+                false => prop.key.static_name().map(|name| escape_html(&name, false)),
+            };
+            let value = ctx.ast.move_expression(&mut prop.value);
+            if let ast::Expression::BooleanLiteral(lit) = &value {
+                if !lit.value {
+                    continue;
+                }
+                if let Some(name) = &name {
+                    if let Some(quasi) = quasis.last_mut() {
+                        if i > 0 {
+                            quasi.push(' ');
+                        }
+                        quasi.push_str(name);
+                        quasi.push_str(separator);
+                    }
+                    continue;
+                }
+            }
+            let key = match (name, prop.key.as_expression_mut()) {
+                (Some(name), _) => ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(&name)),
+                (None, Some(key)) => {
+                    let key = ctx.ast.move_expression(key);
+                    let escape = self.register_import_method("escape", ctx);
+                    let attr = ctx.ast.expression_boolean_literal(SPAN, true);
+                    call_expression(escape, [key, attr], ctx)
+                }
+                (None, None) => continue,
+            };
+            values.push(match value {
+                ast::Expression::BooleanLiteral(_) => key,
+                value => ctx.ast.expression_conditional(
+                    SPAN,
+                    value,
+                    key,
+                    ctx.ast.expression_string_literal(SPAN, ""),
+                ),
+            });
+            quasis.push(separator.to_string());
+        }
+    }
+
+    fn transform_children_ssr(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        results: &mut TransformResult<'a>,
+        do_not_escape: bool,
+        markers: bool,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let info = TransformInfo {
+            do_not_escape,
+            ..Default::default()
+        };
+        for child in el
+            .children
+            .iter_mut()
+            .filter(|child| is_meaningful_child(child))
+        {
+            // a head inside of the document isn't hydrated
+            if let ast::JSXChild::Element(head) = child {
+                if self.config.hydratable && get_tag_name(head) == "head" {
+                    let info = TransformInfo {
+                        do_not_escape,
+                        no_hydration: true,
+                        ..Default::default()
+                    };
+                    let head = self.transform_element_ssr(head, ctx, &info);
+                    let head = head.create_template(
+                        &self.config,
+                        ctx,
+                        &mut self.template_ctx,
+                        &mut self.imports,
+                        false,
+                    );
+                    results.template_parts.push(String::new());
+                    results
+                        .template_values
+                        .push(self.no_hydration_ssr(head, ctx));
+                    continue;
+                }
+            }
+            let Some(mut child) = self.transform_node(child, ctx, &info) else {
+                continue;
+            };
+            match (child.template_parts.is_empty(), &child.template) {
+                (false, _) => append_parts(&mut results.template_parts, child.template_parts),
+                (true, Some(template)) => append_to_template(&mut results.template_parts, template),
+                (true, None) => {}
+            }
+            results.template_values.extend(child.template_values);
+            if !child.exprs.is_empty() {
+                let expr = child.exprs.remove(0);
+                let expr = match do_not_escape || child.spread_element {
+                    true => expr,
+                    false => self.escape_expression_ssr(expr, false, false, ctx),
+                };
+                // the inserted content is delimited so that the client can find it when hydrating
+                let markers = markers && !child.spread_element;
+                if markers {
+                    append_to_template(&mut results.template_parts, "<!--$-->");
+                }
+                results.template_parts.push(String::new());
+                results.template_values.push(expr);
+                if markers {
+                    append_to_template(&mut results.template_parts, "<!--/-->");
+                }
+            }
+        }
+    }
+
+    /// Escape the values an expression can result in, leaving out the parts that are known to be
+    /// safe
+    pub fn escape_expression_ssr(
+        &mut self,
+        expr: ast::Expression<'a>,
+        attr: bool,
+        escape_literals: bool,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        match expr {
+            ast::Expression::StringLiteral(lit) if escape_literals => ctx
+                .ast
+                .expression_string_literal(lit.span, ctx.ast.atom(&escape_html(&lit.value, attr))),
+            ast::Expression::TemplateLiteral(lit)
+                if escape_literals && lit.expressions.is_empty() =>
+            {
+                let raw = lit
+                    .quasis
+                    .first()
+                    .map(|quasi| escape_html(&quasi.value.raw, attr))
+                    .unwrap_or_default();
+                ctx.ast
+                    .expression_string_literal(lit.span, ctx.ast.atom(&raw))
+            }
+            ast::Expression::StringLiteral(_)
+            | ast::Expression::NumericLiteral(_)
+            | ast::Expression::UnaryExpression(_) => expr,
+            ast::Expression::ArrowFunctionExpression(mut arrow) => {
+                let expression = arrow.expression;
+                self.escape_function_body(&mut arrow.body, expression, attr, escape_literals, ctx);
+                ast::Expression::ArrowFunctionExpression(arrow)
+            }
+            ast::Expression::FunctionExpression(mut func) => {
+                if let Some(body) = &mut func.body {
+                    self.escape_function_body(body, false, attr, escape_literals, ctx);
+                }
+                ast::Expression::FunctionExpression(func)
+            }
+            ast::Expression::TemplateLiteral(mut lit) => {
+                for expr in lit.expressions.iter_mut() {
+                    let value = ctx.ast.move_expression(expr);
+                    *expr = self.escape_expression_ssr(value, attr, escape_literals, ctx);
+                }
+                ast::Expression::TemplateLiteral(lit)
+            }
+            ast::Expression::BinaryExpression(mut bin) => {
+                let left = ctx.ast.move_expression(&mut bin.left);
+                bin.left = self.escape_expression_ssr(left, attr, escape_literals, ctx);
+                let right = ctx.ast.move_expression(&mut bin.right);
+                bin.right = self.escape_expression_ssr(right, attr, escape_literals, ctx);
+                ast::Expression::BinaryExpression(bin)
+            }
+            ast::Expression::ConditionalExpression(mut cond) => {
+                let consequent = ctx.ast.move_expression(&mut cond.consequent);
+                cond.consequent =
+                    self.escape_expression_ssr(consequent, attr, escape_literals, ctx);
+                let alternate = ctx.ast.move_expression(&mut cond.alternate);
+                cond.alternate = self.escape_expression_ssr(alternate, attr, escape_literals, ctx);
+                ast::Expression::ConditionalExpression(cond)
+            }
+            ast::Expression::LogicalExpression(mut logical) => {
+                let right = ctx.ast.move_expression(&mut logical.right);
+                logical.right = self.escape_expression_ssr(right, attr, escape_literals, ctx);
+                // the left side of `&&` is only rendered when it is falsy
+                if logical.operator != LogicalOperator::And {
+                    let left = ctx.ast.move_expression(&mut logical.left);
+                    logical.left = self.escape_expression_ssr(left, attr, escape_literals, ctx);
+                }
+                ast::Expression::LogicalExpression(logical)
+            }
+            ast::Expression::CallExpression(mut call) if call.callee.is_function() => {
+                let callee = ctx.ast.move_expression(&mut call.callee);
+                call.callee = self.escape_expression_ssr(callee, attr, escape_literals, ctx);
+                ast::Expression::CallExpression(call)
+            }
+            ast::Expression::ParenthesizedExpression(mut paren) => {
+                let inner = ctx.ast.move_expression(&mut paren.expression);
+                paren.expression = self.escape_expression_ssr(inner, attr, escape_literals, ctx);
+                ast::Expression::ParenthesizedExpression(paren)
+            }
+            // native elements are rendered to escaped templates already
+            ast::Expression::JSXElement(el) if !is_component(&get_tag_name(&el)) => {
+                self.wont_escape.insert(el.span.start);
+                ast::Expression::JSXElement(el)
+            }
+            expr => {
+                let escape = self.register_import_method("escape", ctx);
+                let mut args = vec![expr];
+                if attr {
+                    args.push(ctx.ast.expression_boolean_literal(SPAN, true));
+                }
+                call_expression(escape, args, ctx)
+            }
+        }
+    }
+
+    fn escape_function_body(
+        &mut self,
+        body: &mut ast::FunctionBody<'a>,
+        expression: bool,
+        attr: bool,
+        escape_literals: bool,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        for statement in body.statements.iter_mut() {
+            let value = match statement {
+                ast::Statement::ReturnStatement(ret) => match &mut ret.argument {
+                    Some(argument) => argument,
+                    None => continue,
+                },
+                ast::Statement::ExpressionStatement(stmt) if expression => &mut stmt.expression,
+                _ => continue,
+            };
+            let expr = ctx.ast.move_expression(value);
+            *value = self.escape_expression_ssr(expr, attr, escape_literals, ctx);
+        }
+    }
+}
+
+/// take the attributes of the element, with their values turned into expressions
+fn collect_attributes<'a>(
+    el: &mut ast::JSXElement<'a>,
+    ctx: &mut TraverseCtx<'a>,
+) -> Vec<AttributeItem<'a>> {
+    let items = std::mem::replace(&mut el.opening_element.attributes, ctx.ast.vec());
+    items
+        .into_iter()
+        .map(|item| match item {
+            ast::JSXAttributeItem::SpreadAttribute(mut spread) => {
+                AttributeItem::Spread(ctx.ast.move_expression(&mut spread.argument))
+            }
+            ast::JSXAttributeItem::Attribute(mut attr) => {
+                let key = match &attr.name {
+                    ast::JSXAttributeName::Identifier(ident) => ident.name.to_string(),
+                    ast::JSXAttributeName::NamespacedName(name) => {
+                        format!("{}:{}", name.namespace.name, name.property.name)
+                    }
+                };
+                let (value, container) =
+                    match attr.value.take() {
+                        None => (None, false),
+                        Some(ast::JSXAttributeValue::StringLiteral(lit)) => (
+                            Some(ctx.ast.expression_string_literal(
+                                lit.span,
+                                decode_html_entities(&lit.value),
+                            )),
+                            false,
+                        ),
+                        Some(ast::JSXAttributeValue::ExpressionContainer(mut container)) => (
+                            container
+                                .expression
+                                .as_expression_mut()
+                                .map(|expr| ctx.ast.move_expression(expr)),
+                            true,
+                        ),
+                        Some(ast::JSXAttributeValue::Element(element)) => {
+                            (Some(ast::Expression::JSXElement(element)), true)
+                        }
+                        Some(ast::JSXAttributeValue::Fragment(frag)) => {
+                            (Some(ast::Expression::JSXFragment(frag)), true)
+                        }
+                    };
+                AttributeItem::Attribute(Attribute {
+                    key,
+                    value,
+                    container,
+                })
+            }
+        })
+        .collect()
+}
+
+/// the positions in the source of the attributes with the prefix
+fn namespaced_attributes(attributes: &[(usize, AttributeItem)], prefix: &str) -> Vec<usize> {
+    attributes
+        .iter()
+        .filter(|(_, item)| {
+            matches!(item, AttributeItem::Attribute(attr) if attr.key.starts_with(prefix))
+        })
+        .map(|(index, _)| *index)
+        .collect()
+}
+
+/// Move the selected namespaced attributes, given by their position in the source, into the
+/// object of the target attribute, or into a new one taking the place of the first of them.
+///
+/// Like the babel plugin, the merged attributes are removed from the list at their position in
+/// the source, so after a removal a later position can miss them and remove another attribute.
+/// The removed attributes are kept aside, since the merge still reads them.
+fn transform_to_object<'a>(
+    attributes: &mut Vec<(usize, AttributeItem<'a>)>,
+    removed: &mut Vec<(usize, AttributeItem<'a>)>,
+    selected: &[usize],
+    target: &str,
+    ctx: &TraverseCtx<'a>,
+) {
+    if selected.is_empty() {
+        return;
+    }
+    let existing = attributes.iter().find_map(|(index, item)| match item {
+        AttributeItem::Attribute(attr) if attr.key == target => Some(*index),
+        _ => None,
+    });
+
+    let mut properties = ctx.ast.vec();
+    for (i, index) in selected.iter().enumerate() {
+        if let Some(AttributeItem::Attribute(attr)) = find_attribute(attributes, removed, *index) {
+            let name = attr
+                .key
+                .split_once(':')
+                .map_or("", |(_, name)| name)
+                .to_string();
+            let value = match &attr.value {
+                Some(value) => value.clone_in(ctx.ast.allocator),
+                None => ctx.ast.expression_boolean_literal(SPAN, true),
+            };
+            properties.push(object_property(&name, value, ctx));
+        }
+        if (existing.is_some() || i > 0) && *index < attributes.len() {
+            removed.push(attributes.remove(*index));
+        }
+    }
+
+    let existing = existing.and_then(|index| find_attribute(attributes, removed, index));
+    match existing {
+        Some(AttributeItem::Attribute(Attribute {
+            value: Some(ast::Expression::ObjectExpression(obj)),
+            container: true,
+            ..
+        })) => obj.properties.extend(properties),
+        _ => {
+            if let Some(item) = find_attribute(attributes, removed, selected[0]) {
+                *item = AttributeItem::Attribute(Attribute {
+                    key: target.to_string(),
+                    value: Some(ctx.ast.expression_object(SPAN, properties, None)),
+                    container: true,
+                });
+            }
+        }
+    }
+}
+
+/// the attribute at the position in the source, whether it is still in the list or was removed
+fn find_attribute<'a, 'b>(
+    attributes: &'b mut [(usize, AttributeItem<'a>)],
+    removed: &'b mut [(usize, AttributeItem<'a>)],
+    index: usize,
+) -> Option<&'b mut AttributeItem<'a>> {
+    attributes
+        .iter_mut()
+        .chain(removed.iter_mut())
+        .find(|(original, _)| *original == index)
+        .map(|(_, item)| item)
+}
+
+fn has_spread(obj: &ast::ObjectExpression) -> bool {
+    obj.properties
+        .iter()
+        .any(|prop| matches!(prop, ast::ObjectPropertyKind::SpreadProperty(_)))
+}
+
+/// append the text to the last string of the template
+fn append_to_template(template: &mut Vec<String>, text: &str) {
+    match template.last_mut() {
+        Some(last) => last.push_str(text),
+        None => template.push(text.to_string()),
+    }
+}
+
+/// append the strings of another template, joining its first string with the last one
+fn append_parts(template: &mut Vec<String>, parts: Vec<String>) {
+    let mut parts = parts.into_iter();
+    if let Some(first) = parts.next() {
+        append_to_template(template, &first);
+    }
+    template.extend(parts);
+}
@@ -0,0 +1,137 @@
+use oxc::{
+    allocator::Vec as OxcVec,
+    ast::{ast, NONE},
+    semantic::SymbolFlags,
+    span::SPAN,
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::{
+    shared::{
+        imports::ImportManager,
+        transform::{JsxTransform, Template, TemplateCreationCtx, TransformResult},
+        utils::call_expression,
+    },
+    Config, OutputType,
+};
+
+impl<'a> TransformResult<'a> {
+    pub fn create_template_ssr(
+        mut self,
+        config: &Config,
+        traverse_ctx: &mut TraverseCtx<'a>,
+        creation_ctx: &mut TemplateCreationCtx<'a>,
+        imports: &mut ImportManager<'a>,
+    ) -> ast::Expression<'a> {
+        if self.template_parts.is_empty() {
+            return match self.exprs.is_empty() {
+                true => traverse_ctx.ast.expression_null_literal(SPAN),
+                false => self.exprs.remove(0),
+            };
+        }
+
+        let parts = self.template_parts;
+        let single = parts.len() == 1;
+        let template_id = match creation_ctx
+            .templates
+            .iter()
+            .find(|t| t.renderer == OutputType::Ssr && t.parts == parts)
+        {
+            Some(existing) => existing.id.clone(),
+            None => {
+                let id = traverse_ctx
+                    .generate_uid_in_root_scope("tmpl$", SymbolFlags::FunctionScopedVariable)
+                    .name;
+                creation_ctx.templates.push(Template {
+                    id: id.clone(),
+                    template: String::new(),
+                    renderer: OutputType::Ssr,
+                    is_svg: false,
+                    is_import_node: false,
+                    parts,
+                });
+                id
+            }
+        };
+        let template_id = traverse_ctx
+            .ast
+            .expression_identifier_reference(SPAN, template_id);
+
+        // a static element inside of an escaped expression can be inserted as it is
+        if self.wont_escape && single {
+            return template_id;
+        }
+        let ssr = imports.add("ssr", &config.module_name, traverse_ctx);
+        let mut args = vec![template_id];
+        if !single {
+            args.extend(self.template_values);
+        }
+        call_expression(
+            traverse_ctx.ast.expression_identifier_reference(SPAN, ssr),
+            args,
+            traverse_ctx,
+        )
+    }
+}
+
+impl<'a> JsxTransform<'a> {
+    /// hoist the collected templates to the top of the program, as a string or as an array of
+    /// the strings the values are interpolated between
+    pub fn append_templates_ssr(
+        &mut self,
+        program: &mut ast::Program<'a>,
+        templates: Vec<Template<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let mut declarators: OxcVec<'a, ast::VariableDeclarator<'a>> = ctx.ast.vec();
+        for template in templates
+            .into_iter()
+            .filter(|t| t.renderer == OutputType::Ssr)
+        {
+            let mut strings = template
+                .parts
+                .into_iter()
+                .map(|part| ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(&part)))
+                .collect::<Vec<_>>();
+            let init = match strings.len() {
+                1 => strings.remove(0),
+                _ => ctx.ast.expression_array(
+                    SPAN,
+                    ctx.ast.vec_from_iter(
+                        strings
+                            .into_iter()
+                            .map(|string| ctx.ast.array_expression_element_expression(string)),
+                    ),
+                    None,
+                ),
+            };
+            declarators.push(
+                ctx.ast.variable_declarator(
+                    SPAN,
+                    ast::VariableDeclarationKind::Var,
+                    ctx.ast.binding_pattern(
+                        ctx.ast
+                            .binding_pattern_kind_binding_identifier(SPAN, template.id),
+                        NONE,
+                        false,
+                    ),
+                    Some(init),
+                    false,
+                ),
+            );
+        }
+        if declarators.is_empty() {
+            return;
+        }
+
+        program.body.insert(
+            0,
+            ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                declarators,
+                false,
+            )),
+        );
+    }
+}
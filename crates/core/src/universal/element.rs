@@ -0,0 +1,361 @@
+use oxc::{
+    ast::ast,
+    semantic::SymbolFlags,
+    span::{Atom, SPAN},
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::{
+    shared::{
+        constants::RESERVED_NAMESPACES,
+        transform::{DynamicAttr, JsxTransform, TransformInfo, TransformResult},
+        utils::{
+            arrow_function, call_expression, check_length, escape_template, get_tag_name,
+            is_meaningful_child, static_expression_value, var_declarator, DynamicCheck,
+        },
+    },
+    OutputType,
+};
+
+impl<'a> JsxTransform<'a> {
+    pub fn transform_element_universal(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+        info: &TransformInfo,
+    ) -> TransformResult<'a> {
+        let tag_name = get_tag_name(el);
+        // every element is created by the renderer, so each of them needs a reference
+        let id = ctx
+            .generate_uid_in_current_scope("el$", SymbolFlags::FunctionScopedVariable)
+            .name;
+        let create_element = self.register_renderer_method("createElement", "universal", ctx);
+        let tag = ctx
+            .ast
+            .expression_string_literal(SPAN, ctx.ast.atom(&tag_name));
+        let mut results = TransformResult {
+            id: Some(id.clone()),
+            tag_name: Some(tag_name),
+            renderer: Some(OutputType::Universal),
+            declarations: ctx.ast.vec1(var_declarator(
+                id,
+                call_expression(create_element, [tag], ctx),
+                ctx,
+            )),
+            ..TransformResult::new(ctx)
+        };
+
+        self.transform_attributes_universal(el, &mut results, ctx);
+        self.transform_children_universal(el, &mut results, ctx);
+
+        if info.top_level {
+            let dynamics = std::mem::take(&mut results.dynamics);
+            if let Some(effect) = self.wrap_dynamics_universal(dynamics, ctx) {
+                results.exprs.push(effect);
+            }
+            let post_exprs = std::mem::replace(&mut results.post_exprs, ctx.ast.vec());
+            results.exprs.extend(post_exprs);
+        }
+        results
+    }
+
+    /// Generate the code setting the attributes through the renderer, which decides itself
+    /// whether they are attributes or properties
+    fn transform_attributes_universal(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let Some(elem) = results.id.clone() else {
+            return;
+        };
+        let elem_ref =
+            |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, elem.clone());
+        let has_children = !el.children.is_empty();
+
+        // from the first spread on, attributes are merged into the props passed to `spread`
+        let mut spread_args = Vec::new();
+        let mut running_object = ctx.ast.vec();
+        let mut dynamic_spread = false;
+        let items = std::mem::replace(&mut el.opening_element.attributes, ctx.ast.vec());
+        for item in items {
+            let mut attr = match item {
+                ast::JSXAttributeItem::Attribute(attr) => attr,
+                ast::JSXAttributeItem::SpreadAttribute(mut spread) => {
+                    if !running_object.is_empty() {
+                        spread_args.push(ctx.ast.expression_object(
+                            SPAN,
+                            std::mem::replace(&mut running_object, ctx.ast.vec()),
+                            None,
+                        ));
+                    }
+                    let argument = ctx.ast.move_expression(&mut spread.argument);
+                    let (argument, dynamic) = self.transform_spread_argument(argument, ctx);
+                    dynamic_spread |= dynamic;
+                    spread_args.push(argument);
+                    continue;
+                }
+            };
+            let key = match &attr.name {
+                ast::JSXAttributeName::Identifier(ident) => ident.name.to_string(),
+                ast::JSXAttributeName::NamespacedName(name) => {
+                    format!("{}:{}", name.namespace.name, name.property.name)
+                }
+            };
+            // refs, directives, properties and events are still handled on their own
+            if !spread_args.is_empty()
+                && !(key == "ref"
+                    || key.starts_with("use:")
+                    || key.starts_with("prop:")
+                    || key.starts_with("on"))
+            {
+                if !(has_children && key == "children") {
+                    if let Some(prop) = self.spread_property(&key, attr.value.take(), ctx) {
+                        running_object.push(prop);
+                    }
+                }
+                continue;
+            }
+
+            // unlike the DOM and SSR output, entities are handed to the renderer as they are
+            // written, like `escapeAttribute` of the universal textInterpolation fixture expects
+            let (value, mut container) = match attr.value.take() {
+                None => (None, false),
+                Some(ast::JSXAttributeValue::StringLiteral(lit)) => (
+                    Some(ctx.ast.expression_string_literal(lit.span, lit.value)),
+                    false,
+                ),
+                Some(ast::JSXAttributeValue::ExpressionContainer(mut container)) => {
+                    // `children` on an element is the same as passing them as children
+                    if key == "children" && !has_children {
+                        el.children
+                            .push(ast::JSXChild::ExpressionContainer(container));
+                        continue;
+                    }
+                    let value = container
+                        .expression
+                        .as_expression_mut()
+                        .map(|expr| ctx.ast.move_expression(expr));
+                    (value, true)
+                }
+                Some(ast::JSXAttributeValue::Element(element)) => {
+                    (Some(ast::Expression::JSXElement(element)), true)
+                }
+                Some(ast::JSXAttributeValue::Fragment(frag)) => {
+                    (Some(ast::Expression::JSXFragment(frag)), true)
+                }
+            };
+            let mut value = value.unwrap_or_else(|| ctx.ast.expression_boolean_literal(SPAN, true));
+
+            // values that can be evaluated at compile time are treated like string literals
+            if container && !key.starts_with("use:") {
+                if let Some(evaluated) = static_expression_value(&value) {
+                    value = ctx.ast.expression_string_literal(SPAN, evaluated);
+                    container = false;
+                }
+            }
+            let reserved = key
+                .split_once(':')
+                .is_some_and(|(namespace, _)| RESERVED_NAMESPACES.contains(&namespace));
+            if !(container || reserved) {
+                let set = self.set_prop_universal(&elem, &key, value, None, ctx);
+                results.exprs.push(set);
+                continue;
+            }
+
+            if key == "ref" {
+                self.transform_ref("universal", elem.clone(), value, results, ctx);
+            } else if let Some(name) = key.strip_prefix("use:") {
+                let use_fn = self.register_renderer_method("use", "universal", ctx);
+                let directive = ctx
+                    .ast
+                    .expression_identifier_reference(SPAN, ctx.ast.atom(name));
+                let accessor = arrow_function(ctx.ast.vec(), value, ctx);
+                results.exprs.insert(
+                    0,
+                    call_expression(use_fn, [directive, elem_ref(ctx), accessor], ctx),
+                );
+            } else if key == "children" {
+                // the children of the element take precedence
+            } else if self.config.effect_wrapper.is_some()
+                && self.is_dynamic(
+                    &value,
+                    DynamicCheck {
+                        check_member: true,
+                        ..Default::default()
+                    },
+                    ctx,
+                )
+            {
+                results.dynamics.push(DynamicAttr {
+                    elem: elem.clone(),
+                    key,
+                    value,
+                    is_svg: false,
+                    is_ce: false,
+                    tag_name: results.tag_name.clone().unwrap_or_default(),
+                    span: attr.span,
+                });
+            } else {
+                let set = self.set_prop_universal(&elem, &key, value, None, ctx);
+                results.exprs.push(set);
+            }
+        }
+
+        if !spread_args.is_empty() {
+            if !running_object.is_empty() {
+                spread_args.push(ctx.ast.expression_object(SPAN, running_object, None));
+            }
+            let props = match spread_args.len() > 1 || dynamic_spread {
+                true => {
+                    let merge_props = self.register_import_method("mergeProps", ctx);
+                    call_expression(merge_props, spread_args, ctx)
+                }
+                false => spread_args.remove(0),
+            };
+            let spread = self.register_renderer_method("spread", "universal", ctx);
+            let call = call_expression(
+                spread,
+                [
+                    elem_ref(ctx),
+                    props,
+                    ctx.ast.expression_boolean_literal(SPAN, has_children),
+                ],
+                ctx,
+            );
+            results.exprs.push(call);
+        }
+    }
+
+    /// create the call handing the attribute to the renderer, along with the previous value when
+    /// it is updated by an effect
+    pub fn set_prop_universal(
+        &mut self,
+        elem: &Atom<'a>,
+        name: &str,
+        value: ast::Expression<'a>,
+        prev_id: Option<ast::Expression<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> ast::Expression<'a> {
+        let set_prop = self.register_renderer_method("setProp", "universal", ctx);
+        let mut args = vec![
+            ctx.ast.expression_identifier_reference(SPAN, elem.clone()),
+            ctx.ast.expression_string_literal(SPAN, ctx.ast.atom(name)),
+            value,
+        ];
+        args.extend(prev_id);
+        call_expression(set_prop, args, ctx)
+    }
+
+    /// Create the children and append them to the element, inserting the expressions before the
+    /// following node
+    fn transform_children_universal(
+        &mut self,
+        el: &mut ast::JSXElement<'a>,
+        results: &mut TransformResult<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let Some(parent_id) = results.id.clone() else {
+            return;
+        };
+        let parent_ref = |ctx: &TraverseCtx<'a>| {
+            ctx.ast
+                .expression_identifier_reference(SPAN, parent_id.clone())
+        };
+        let multi = check_length(&el.children);
+
+        let mut child_nodes: Vec<TransformResult<'a>> = Vec::new();
+        for child in el
+            .children
+            .iter_mut()
+            .filter(|child| is_meaningful_child(child))
+        {
+            let Some(transformed) = self.transform_node(child, ctx, &TransformInfo::default())
+            else {
+                continue;
+            };
+            // adjacent texts end up in a single text node
+            match child_nodes.last_mut() {
+                Some(prev) if transformed.text && prev.text => {
+                    if let (Some(prev_template), Some(template)) =
+                        (&mut prev.template, &transformed.template)
+                    {
+                        prev_template.push_str(template);
+                    }
+                }
+                _ => child_nodes.push(transformed),
+            }
+        }
+
+        // the closest following sibling that can be used as the insertion point
+        let mut next_ids = vec![None; child_nodes.len()];
+        for index in (0..child_nodes.len().saturating_sub(1)).rev() {
+            next_ids[index] = child_nodes[index + 1]
+                .id
+                .clone()
+                .or_else(|| next_ids[index + 1].clone());
+        }
+
+        // the nodes are appended before anything else runs on them
+        let mut appends = Vec::new();
+        for (index, mut child) in child_nodes.into_iter().enumerate() {
+            if let Some(child_id) = child.id.clone() {
+                let node = match (child.text, &child.template) {
+                    (true, Some(text)) => {
+                        let create_text_node =
+                            self.register_renderer_method("createTextNode", "universal", ctx);
+                        let quasi = ctx.ast.template_element(
+                            SPAN,
+                            true,
+                            ast::TemplateElementValue {
+                                raw: ctx.ast.atom(&escape_template(text)),
+                                cooked: Some(ctx.ast.atom(text)),
+                            },
+                        );
+                        let text = ctx.ast.expression_template_literal(
+                            SPAN,
+                            ctx.ast.vec1(quasi),
+                            ctx.ast.vec(),
+                        );
+                        let text_node = call_expression(create_text_node, [text], ctx);
+                        // a text node next to expressions is referenced as their insertion point
+                        match multi {
+                            true => {
+                                results.declarations.push(var_declarator(
+                                    child_id.clone(),
+                                    text_node,
+                                    ctx,
+                                ));
+                                ctx.ast.expression_identifier_reference(SPAN, child_id)
+                            }
+                            false => text_node,
+                        }
+                    }
+                    _ => ctx.ast.expression_identifier_reference(SPAN, child_id),
+                };
+                let insert_node = self.register_renderer_method("insertNode", "universal", ctx);
+                appends.push(call_expression(insert_node, [parent_ref(ctx), node], ctx));
+                results.declarations.extend(child.declarations);
+                results.exprs.extend(child.exprs);
+                results
+                    .inline_declarations
+                    .extend(child.inline_declarations);
+                results.dynamics.extend(child.dynamics);
+                results.post_exprs.extend(child.post_exprs);
+            } else if !child.exprs.is_empty() {
+                let insert = self.register_renderer_method("insert", "universal", ctx);
+                let mut args = vec![parent_ref(ctx), child.exprs.remove(0)];
+                if multi {
+                    args.push(match next_ids[index].clone() {
+                        Some(next_id) => ctx.ast.expression_identifier_reference(SPAN, next_id),
+                        None => ctx.ast.expression_null_literal(SPAN),
+                    });
+                }
+                results.exprs.push(call_expression(insert, args, ctx));
+            }
+        }
+        let exprs = std::mem::replace(&mut results.exprs, ctx.ast.vec_from_iter(appends));
+        results.exprs.extend(exprs);
+    }
+}
@@ -0,0 +1,169 @@
+use oxc::{
+    ast::ast,
+    semantic::SymbolFlags,
+    span::SPAN,
+    syntax::operator::{BinaryOperator, LogicalOperator},
+};
+use oxc_traverse::TraverseCtx;
+
+use crate::{
+    dom::template::numbered_id,
+    shared::{
+        imports::ImportManager,
+        transform::{DynamicAttr, JsxTransform, TransformResult},
+        utils::{
+            arrow_function, arrow_function_block, assign_member, call_expression, formal_parameter,
+            iife, object_property, static_member, var_declarator,
+        },
+    },
+    Config,
+};
+
+impl<'a> TransformResult<'a> {
+    pub fn create_template_universal(
+        mut self,
+        config: &Config,
+        traverse_ctx: &mut TraverseCtx<'a>,
+        imports: &mut ImportManager<'a>,
+        wrap: bool,
+    ) -> ast::Expression<'a> {
+        let Some(id) = self.id.clone() else {
+            let expr = match self.exprs.is_empty() {
+                true => traverse_ctx.ast.expression_null_literal(SPAN),
+                false => self.exprs.remove(0),
+            };
+            if let (true, Some(memo_wrapper)) = (wrap && self.dynamic, &config.memo_wrapper) {
+                let memo = imports.add(memo_wrapper, &config.module_name, traverse_ctx);
+                return call_expression(
+                    traverse_ctx.ast.expression_identifier_reference(SPAN, memo),
+                    [expr],
+                    traverse_ctx,
+                );
+            }
+            return expr;
+        };
+
+        // an element without anything to set on it is the call creating it
+        if self.exprs.is_empty() && self.declarations.len() == 1 {
+            if let Some(init) = self.declarations.remove(0).init {
+                return init;
+            }
+        }
+
+        let mut statements = traverse_ctx.ast.vec();
+        statements.push(traverse_ctx.ast.statement_declaration(
+            traverse_ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                self.declarations,
+                false,
+            ),
+        ));
+        statements.extend(TransformResult::expr_statements(
+            self.exprs,
+            &self.inline_declarations,
+            traverse_ctx,
+        ));
+        statements.push(traverse_ctx.ast.statement_return(
+            SPAN,
+            Some(traverse_ctx.ast.expression_identifier_reference(SPAN, id)),
+        ));
+        iife(statements, traverse_ctx)
+    }
+}
+
+impl<'a> JsxTransform<'a> {
+    /// wrap the updates of the dynamic attributes in a single effect, handing the renderer the
+    /// previous value of each of them
+    pub fn wrap_dynamics_universal(
+        &mut self,
+        dynamics: Vec<DynamicAttr<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<ast::Expression<'a>> {
+        if dynamics.is_empty() {
+            return None;
+        }
+        let effect_wrapper = self.config.effect_wrapper.clone()?;
+        let effect = self.register_import_method(&effect_wrapper, ctx);
+
+        if dynamics.len() == 1 {
+            let dynamic = dynamics.into_iter().next()?;
+            let prev_value = ctx.ast.atom("_$p");
+            let prev_id = ctx
+                .ast
+                .expression_identifier_reference(SPAN, prev_value.clone());
+            let set = self.set_prop_universal(
+                &dynamic.elem,
+                &dynamic.key,
+                dynamic.value,
+                Some(prev_id),
+                ctx,
+            );
+            let arrow_fn =
+                arrow_function(ctx.ast.vec1(formal_parameter(prev_value, ctx)), set, ctx);
+            return Some(call_expression(effect, [arrow_fn], ctx));
+        }
+
+        let prev_value = ctx.ast.atom("_p$");
+        let prev = |ctx: &TraverseCtx<'a>| {
+            ctx.ast
+                .expression_identifier_reference(SPAN, prev_value.clone())
+        };
+        let mut declarations = ctx.ast.vec();
+        let mut statements = ctx.ast.vec();
+        let mut properties = ctx.ast.vec();
+        for (index, dynamic) in dynamics.into_iter().enumerate() {
+            let id = numbered_id(index);
+            let var = ctx
+                .generate_uid_in_current_scope("v$", SymbolFlags::FunctionScopedVariable)
+                .name;
+            let var_ref =
+                |ctx: &TraverseCtx<'a>| ctx.ast.expression_identifier_reference(SPAN, var.clone());
+            declarations.push(var_declarator(var.clone(), dynamic.value, ctx));
+            properties.push(object_property(
+                &id,
+                ctx.ast.expression_identifier_reference(SPAN, "undefined"),
+                ctx,
+            ));
+
+            // `_v$ !== _p$.e && (_p$.e = setProp(el, key, _v$, _p$.e))`
+            let prev_id = static_member(prev(ctx), &id, ctx);
+            let set = self.set_prop_universal(
+                &dynamic.elem,
+                &dynamic.key,
+                var_ref(ctx),
+                Some(prev_id),
+                ctx,
+            );
+            let assign = assign_member(prev(ctx), &id, set, ctx);
+            let changed = ctx.ast.expression_binary(
+                SPAN,
+                var_ref(ctx),
+                BinaryOperator::StrictInequality,
+                static_member(prev(ctx), &id, ctx),
+            );
+            let statement = ctx
+                .ast
+                .expression_logical(SPAN, changed, LogicalOperator::And, assign);
+            statements.push(ctx.ast.statement_expression(SPAN, statement));
+        }
+
+        statements.insert(
+            0,
+            ctx.ast.statement_declaration(ctx.ast.declaration_variable(
+                SPAN,
+                ast::VariableDeclarationKind::Var,
+                declarations,
+                false,
+            )),
+        );
+        statements.push(ctx.ast.statement_return(SPAN, Some(prev(ctx))));
+        let arrow_fn = arrow_function_block(
+            ctx.ast.vec1(formal_parameter(prev_value.clone(), ctx)),
+            statements,
+            ctx,
+        );
+        let initial = ctx.ast.expression_object(SPAN, properties, None);
+        Some(call_expression(effect, [arrow_fn, initial], ctx))
+    }
+}
@@ -0,0 +1,52 @@
+use oxc_jsx_dom_expressions::*;
+
+#[test]
+fn deserializes_babel_options() {
+    let config: Config = serde_json::from_str(
+        r#"{
+            "moduleName": "r-dom",
+            "generate": "ssr",
+            "hydratable": true,
+            "builtIns": ["For", "Show"],
+            "memoWrapper": false
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(config.module_name, "r-dom");
+    assert_eq!(config.generate, OutputType::Ssr);
+    assert!(config.hydratable);
+    assert_eq!(config.built_ins, ["For", "Show"]);
+    assert_eq!(config.effect_wrapper.as_deref(), Some("effect"));
+    assert_eq!(config.memo_wrapper, None);
+}
+
+#[test]
+fn rejects_unknown_options() {
+    let error = serde_json::from_str::<Config>(r#"{ "moduleNmae": "r-dom" }"#).err();
+    assert!(error.is_some_and(|error| error.to_string().contains("moduleNmae")));
+}
+
+#[test]
+fn rejects_unknown_renderer_options() {
+    let error = serde_json::from_str::<Config>(
+        r#"{ "renderers": [{ "name": "dom", "moduleName": "r-dom", "elements": [], "tags": [] }] }"#,
+    )
+    .err();
+    assert!(error.is_some_and(|error| error.to_string().contains("tags")));
+}
+
+#[test]
+fn generates_json_schema() {
+    let schema = serde_json::to_value(Config::json_schema()).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    assert!(properties.contains_key("moduleName"));
+    assert!(properties.contains_key("effectWrapper"));
+    assert!(!properties.contains_key("module_name"));
+    assert_eq!(schema["additionalProperties"], false);
+    let renderer = &schema["definitions"]["RendererConfig"];
+    assert_eq!(renderer["additionalProperties"], false);
+    assert_eq!(
+        renderer["required"],
+        serde_json::json!(["elements", "moduleName", "name"])
+    );
+}
@@ -0,0 +1,51 @@
+use oxc_jsx_dom_expressions::*;
+
+#[test]
+fn reports_syntax_errors() {
+    let error = transform("const template = <div>;".to_string(), Config::default()).unwrap_err();
+    assert!(!error.diagnostics.is_empty());
+}
+
+#[test]
+fn reports_event_handlers_without_expressions() {
+    let error = transform(
+        r#"const template = <div on:click="handler" />;"#.to_string(),
+        Config::default(),
+    )
+    .unwrap_err();
+    assert_eq!(error.diagnostics.len(), 1);
+    assert!(error.to_string().contains("on:click"));
+}
+
+#[test]
+fn reports_malformed_event_handler_arrays() {
+    for handler in ["[]", "[handler, data, extra]", "[...handlers]"] {
+        let source = format!("const template = <div onClick={{{}}} />;", handler);
+        let error = transform(source, Config::default()).unwrap_err();
+        assert_eq!(error.diagnostics.len(), 1, "{}", handler);
+        assert!(error.to_string().contains("onClick"), "{}", handler);
+    }
+}
+
+#[test]
+fn parses_by_the_extension_of_the_file() {
+    let source = "const identity = <T,>(value: T) => value;".to_string();
+    let options = |filename: &str| TransformOptions {
+        filename: Some(filename.to_string()),
+        ..Default::default()
+    };
+    assert!(transform_with_options(source.clone(), Config::default(), options("a.tsx")).is_ok());
+    assert!(transform_with_options(source, Config::default(), options("a.jsx")).is_err());
+}
+
+#[test]
+fn parses_by_the_module_kind_of_the_file() {
+    let source = "import { name } from './name';\nconst template = <div>{name}</div>;".to_string();
+    let options = |filename: &str| TransformOptions {
+        filename: Some(filename.to_string()),
+        ..Default::default()
+    };
+    assert!(transform_with_options(source.clone(), Config::default(), options("a.mjs")).is_ok());
+    // a script can't import
+    assert!(transform_with_options(source, Config::default(), options("a.cjs")).is_err());
+}
@@ -1 +1,4 @@
+mod config;
+mod errors;
+mod source_map;
 mod transform;
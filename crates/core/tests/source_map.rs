@@ -0,0 +1,130 @@
+use oxc_jsx_dom_expressions::*;
+
+#[test]
+fn generates_source_map() {
+    let output = transform_with_options(
+        "const template = <div>{props.name}</div>;".to_string(),
+        Config::default(),
+        TransformOptions {
+            filename: Some("template.tsx".to_string()),
+            source_maps: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let map = output.map.unwrap();
+    assert_eq!(
+        map.get_sources()
+            .map(|source| source.to_string())
+            .collect::<Vec<_>>(),
+        ["template.tsx"]
+    );
+}
+
+#[test]
+fn names_the_source_after_its_type_without_filename() {
+    let output = transform_with_options(
+        "const template = <div>{props.name}</div>;".to_string(),
+        Config::default(),
+        TransformOptions {
+            source_type: Some(SourceType::jsx()),
+            source_maps: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let map = output.map.unwrap();
+    assert_eq!(
+        map.get_sources()
+            .map(|source| source.to_string())
+            .collect::<Vec<_>>(),
+        ["input.jsx"]
+    );
+}
+
+/// the line and column of the first occurrence of the text in the code
+fn position(code: &str, text: &str) -> (u32, u32) {
+    let offset = code.find(text).unwrap();
+    let line_start = code[..offset].rfind('\n').map_or(0, |index| index + 1);
+    (
+        code[..offset].matches('\n').count() as u32,
+        (offset - line_start) as u32,
+    )
+}
+
+#[test]
+fn maps_generated_calls_to_their_attributes_and_children() {
+    let source = "const template = (\n  <div class={props.cls}>\n    {props.name}\n  </div>\n);";
+    let output = transform_with_options(
+        source.to_string(),
+        Config::default(),
+        TransformOptions {
+            source_maps: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let map = output.map.unwrap();
+    let lookup_table = map.generate_lookup_table();
+    let original = |text: &str| {
+        let (line, col) = position(&output.code, text);
+        let token = map.lookup_token(&lookup_table, line, col).unwrap();
+        (token.get_src_line(), token.get_src_col())
+    };
+    assert_eq!(original("_$effect("), (1, 7));
+    assert_eq!(original("_$insert("), (2, 4));
+}
+
+#[test]
+fn drops_positions_missing_from_the_input_map() {
+    let source = "const a = 1;\nconst template = <div>{props.name}</div>;";
+    // only the first line of the input comes from the original source
+    let mut builder = sourcemap::SourceMapBuilder::default();
+    let source_id = builder.set_source_and_content("original.tsx", "const a = 1;");
+    builder.add_token(0, 0, 0, 0, Some(source_id), None);
+    let output = transform_with_options(
+        source.to_string(),
+        Config::default(),
+        TransformOptions {
+            source_maps: true,
+            input_source_map: Some(builder.into_sourcemap()),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let (line, _) = position(&output.code, "const a");
+    let map = output.map.unwrap();
+    assert!(map.get_tokens().count() > 0);
+    assert!(map.get_tokens().all(|token| token.get_dst_line() == line));
+}
+
+#[test]
+fn keeps_the_source_and_its_positions_with_many_templates() {
+    let source = (0..16)
+        .map(|index| format!("const a{0} = <div id=\"a{0}\">{{props.a{0}}}</div>;", index))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let output = transform_with_options(
+        source.clone(),
+        Config::default(),
+        TransformOptions {
+            source_maps: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(output.code.matches("/*#__PURE__*/").count(), 16);
+    let map = output.map.unwrap();
+    assert_eq!(map.get_source_content(0).unwrap().as_ref(), source);
+    let lines = source.lines().collect::<Vec<_>>();
+    for token in map.get_tokens() {
+        let line = lines[token.get_src_line() as usize];
+        assert!((token.get_src_col() as usize) <= line.len());
+    }
+    let lookup_table = map.generate_lookup_table();
+    for index in [0, 15] {
+        let (line, col) = position(&output.code, &format!("props.a{}", index));
+        let token = map.lookup_token(&lookup_table, line, col).unwrap();
+        assert_eq!(token.get_src_line(), index);
+    }
+}
@@ -0,0 +1,10 @@
+import { template as _$template } from "r-dom";
+import { insert as _$insert } from "r-dom";
+var _tmpl$ = /*#__PURE__*/ _$template(`<span>`),
+  _tmpl$2 = /*#__PURE__*/ _$template(`<div>Hello`);
+const template = (() => {
+  var _el$ = _tmpl$();
+  _$insert(_el$, () => state.interpolation);
+  return _el$;
+})();
+const template2 = _tmpl$2();
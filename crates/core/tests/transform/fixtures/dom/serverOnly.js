@@ -0,0 +1,3 @@
+const template = <span $ServerOnly>{state.interpolation}</span>;
+
+const template2 = <div $ServerOnly>Hello</div>;
@@ -1,11 +1,172 @@
+use std::path::Path;
+
 use oxc::{
     allocator::Allocator,
     codegen::{CodeGenerator, CodegenOptions},
     parser::Parser,
     span::SourceType,
 };
+use oxc_jsx_dom_expressions::*;
+use pretty_assertions::assert_eq;
 
 mod dom;
+mod dom_hydratable;
+mod dom_wrapperless;
+mod dynamic;
+mod ssr;
+mod ssr_hydratable;
+mod universal;
+
+/// the fixtures whose output doesn't match the one of the babel plugin yet, one `suite/name` per
+/// line
+const KNOWN_FAILURES: &str = include_str!("known_failures.txt");
+
+/// the elements created by the DOM renderer in the dynamic fixtures, the others are created by
+/// the universal renderer
+const DOM_ELEMENTS: [&str; 23] = [
+    "a",
+    "button",
+    "div",
+    "footer",
+    "h1",
+    "header",
+    "input",
+    "label",
+    "linearGradient",
+    "module",
+    "my-element",
+    "namespace:tag",
+    "noscript",
+    "rect",
+    "script",
+    "slot",
+    "span",
+    "stop",
+    "style",
+    "svg",
+    "table",
+    "tbody",
+    "text",
+];
+
+/// the options the upstream babel tests use for the fixtures of the suite
+fn config(suite: &str) -> Config {
+    let config = Config {
+        built_ins: vec!["For".to_string(), "Show".to_string()],
+        wrap_conditionals: true,
+        context_to_custom_elements: true,
+        static_marker: "@once".to_string(),
+        require_import_source: false,
+        ..Default::default()
+    };
+    match suite {
+        "dom" => Config {
+            module_name: "r-dom".to_string(),
+            generate: OutputType::Dom,
+            ..config
+        },
+        "dom_hydratable" => Config {
+            module_name: "r-dom".to_string(),
+            generate: OutputType::Dom,
+            hydratable: true,
+            ..config
+        },
+        "dom_wrapperless" => Config {
+            module_name: "r-dom".to_string(),
+            generate: OutputType::Dom,
+            effect_wrapper: None,
+            memo_wrapper: None,
+            ..config
+        },
+        "dynamic" => Config {
+            module_name: "r-custom".to_string(),
+            generate: OutputType::Dynamic,
+            renderers: vec![RendererConfig {
+                name: "dom".to_string(),
+                module_name: "r-dom".to_string(),
+                elements: DOM_ELEMENTS.iter().map(|tag| tag.to_string()).collect(),
+            }],
+            ..config
+        },
+        "ssr" => Config {
+            module_name: "r-server".to_string(),
+            generate: OutputType::Ssr,
+            ..config
+        },
+        "ssr_hydratable" => Config {
+            module_name: "r-server".to_string(),
+            generate: OutputType::Ssr,
+            hydratable: true,
+            ..config
+        },
+        "universal" => Config {
+            module_name: "r-custom".to_string(),
+            generate: OutputType::Universal,
+            ..config
+        },
+        _ => panic!("no config for the fixtures of {}", suite),
+    }
+}
+
+/// the fixtures copied from the babel plugin by `copy-fixtures.nu`, whose expected output is
+/// never overwritten
+const UPSTREAM_FIXTURES: &str = "tests/transform/specs";
+
+/// Compare the output of the fixture with the expected one. With `UPDATE_EXPECTED` set, the
+/// expected output of the fixtures of this repository is overwritten instead, except for the
+/// known failures. The output of the upstream fixtures is written next to the build artifacts to
+/// be compared with theirs, and they are still checked.
+fn run_test(input_path: &'static str, expected_path: &'static str, _: &str, _: &str) {
+    let input = Path::new(input_path);
+    let suite = input
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str())
+        .unwrap();
+    let name = input.file_stem().and_then(|name| name.to_str()).unwrap();
+    let fixture = format!("{}/{}", suite, name);
+    let known_failure = KNOWN_FAILURES
+        .lines()
+        .map(str::trim)
+        .any(|line| line == fixture);
+    let upstream = input
+        .parent()
+        .and_then(Path::parent)
+        .is_some_and(|dir| dir.ends_with(UPSTREAM_FIXTURES));
+    let update = std::env::var_os("UPDATE_EXPECTED").is_some();
+    if update && known_failure && !upstream {
+        return;
+    }
+
+    let source = std::fs::read_to_string(input_path).unwrap();
+    let actual = match transform(source, config(suite)) {
+        Ok(code) => code,
+        Err(_) if known_failure => return,
+        Err(error) => panic!("{}", error),
+    };
+
+    if update {
+        if !upstream {
+            std::fs::write(expected_path, actual).unwrap();
+            return;
+        }
+        let output_dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+            .join("specs")
+            .join(suite);
+        std::fs::create_dir_all(&output_dir).unwrap();
+        std::fs::write(output_dir.join(format!("{}.js", name)), &actual).unwrap();
+    }
+    let expected = roundtrip(&std::fs::read_to_string(expected_path).unwrap());
+    if known_failure {
+        assert!(
+            actual != expected,
+            "{} matches the expected output, remove it from the known failures",
+            fixture
+        );
+        return;
+    }
+    assert_eq!(actual, expected);
+}
 
 fn roundtrip(source: &str) -> String {
     let allocator = Allocator::default();
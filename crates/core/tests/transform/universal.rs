@@ -0,0 +1 @@
+tests_macros::gen_tests! {"tests/transform/specs/universal/*.js", crate::transform::run_test, "module"}
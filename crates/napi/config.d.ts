@@ -0,0 +1,53 @@
+/* eslint-disable */
+/**
+ * This file was automatically generated by json-schema-to-typescript.
+ * DO NOT MODIFY IT BY HAND. Instead, modify the source JSONSchema file,
+ * and run json-schema-to-typescript to regenerate this file.
+ */
+
+/**
+ * the name of a wrapper, `false` to disable it or `true` to keep the default one
+ */
+export type Wrapper = string | boolean;
+export type OutputType = "dom" | "ssr" | "universal" | "dynamic";
+
+/**
+ * the options of the transform, deserialized from the same camelCase keys as the options of the babel plugin
+ */
+export interface Config {
+  builtIns?: string[];
+  contextToCustomElements?: boolean;
+  delegateEvents?: boolean;
+  delegatedEvents?: string[];
+  /**
+   * the helper running the updates of dynamic attributes, without it they are only set once
+   */
+  effectWrapper?: Wrapper;
+  generate?: OutputType;
+  hydratable?: boolean;
+  /**
+   * the helper memoizing conditions and dynamic expressions, without it they are passed through unwrapped
+   */
+  memoWrapper?: Wrapper;
+  moduleName?: string;
+  omitNestedClosingTags?: boolean;
+  /**
+   * the renderers owning a set of elements in dynamic mode, the other elements are created by the universal renderer of `module_name`
+   */
+  renderers?: RendererConfig[];
+  requireImportSource?: boolean;
+  staticMarker?: string;
+  validate?: boolean;
+  wrapConditionals?: boolean;
+}
+/**
+ * a renderer that creates some of the elements in dynamic mode
+ */
+export interface RendererConfig {
+  elements: string[];
+  moduleName: string;
+  /**
+   * the backend generating the code for the elements, `dom` or `universal`
+   */
+  name: string;
+}
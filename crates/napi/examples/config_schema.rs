@@ -0,0 +1,5 @@
+/// print the JSON schema of the config, which `config.d.ts` is generated from
+fn main() {
+    let schema = oxc_jsx_dom_expressions::Config::json_schema();
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
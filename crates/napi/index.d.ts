@@ -1,25 +1,45 @@
-/* tslint:disable */
-/* eslint-disable */
+/* eslint-disable */ import type { Config } from './config'; export type { Config, OutputType, RendererConfig, Wrapper } from './config';
 
-/* auto-generated by NAPI-RS */
-
-export interface Config {
-  moduleName?: string
-  generate?: OutputType
-  hydratable?: boolean
-  delegateEvents?: boolean
-  delegatedEvents?: Array<string>
-  builtIns?: Array<string>
-  requireImportSource?: boolean
-  wrapConditionals?: boolean
-  omitNestedClosingTags?: boolean
-  contextToCustomElements?: boolean
-  staticMarker?: string
-  effectWrapper?: string
-  memoWrapper?: string
-  validate?: boolean
+export interface TransformOptions {
+  filename?: string
+  /** the language of the source, implied by the extension of the filename by default */
+  lang?: Lang
+  /**
+   * whether the source is a module or a script, implied by the extension of the filename by
+   * default
+   */
+  sourceType?: ModuleKind
+  sourceMaps?: boolean
+  /** the source map of the input as a JSON string */
+  inputSourceMap?: string
+}
+export const enum Lang {
+  Js = 'js',
+  Jsx = 'jsx',
+  Ts = 'ts',
+  Tsx = 'tsx'
+}
+export const enum ModuleKind {
+  Module = 'module',
+  Script = 'script'
+}
+export interface TransformOutput {
+  code: string
+  /** the source map of the output as a JSON string */
+  map?: string
+}
+export declare function transform(source: string, config?: Config | undefined | null, options?: TransformOptions | undefined | null): string
+export declare function transformWithOptions(source: string, config?: Config | undefined | null, options?: TransformOptions | undefined | null): TransformOutput
+export interface TransformFile {
+  source: string
+  options?: TransformOptions
 }
-export const enum OutputType {
-  Dom = 'dom'
+/** the output of a file of the batch, or the error that kept it from being transformed */
+export interface TransformFileResult {
+  output?: TransformOutput
+  error?: string
 }
-export declare function transform(source: string, config?: Config | undefined | null): string
+/** transform the source on the thread pool of libuv, without blocking the main thread */
+export declare function transformAsync(source: string, config?: Config | undefined | null, options?: TransformOptions | undefined | null): Promise<TransformOutput>
+/** transform the files in parallel, resolving with the result of each of them in their order */
+export declare function transformBatch(files: Array<TransformFile>, config?: Config | undefined | null): Promise<Array<TransformFileResult>>
@@ -310,7 +310,11 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { OutputType, transform } = nativeBinding
+const { Lang, ModuleKind, transform, transformWithOptions, transformAsync, transformBatch } = nativeBinding
 
-module.exports.OutputType = OutputType
+module.exports.Lang = Lang
+module.exports.ModuleKind = ModuleKind
 module.exports.transform = transform
+module.exports.transformWithOptions = transformWithOptions
+module.exports.transformAsync = transformAsync
+module.exports.transformBatch = transformBatch
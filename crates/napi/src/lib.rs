@@ -1,11 +1,238 @@
-use crate::config::Config;
 use napi::bindgen_prelude::*;
 
 use napi_derive::*;
+use oxc_jsx_dom_expressions::{self as core, sourcemap::SourceMap, SourceType, TransformError};
+use rayon::prelude::*;
 
-pub mod config;
+#[napi(object)]
+#[derive(Default)]
+pub struct TransformOptions {
+    pub filename: Option<String>,
+    /// the language of the source, implied by the extension of the filename by default
+    pub lang: Option<Lang>,
+    /// whether the source is a module or a script, implied by the extension of the filename by
+    /// default
+    pub source_type: Option<ModuleKind>,
+    pub source_maps: Option<bool>,
+    /// the source map of the input as a JSON string
+    pub input_source_map: Option<String>,
+}
+
+#[napi(string_enum = "lowercase")]
+pub enum Lang {
+    Js,
+    Jsx,
+    Ts,
+    Tsx,
+}
+
+#[napi(string_enum = "lowercase")]
+pub enum ModuleKind {
+    Module,
+    Script,
+}
+
+impl TransformOptions {
+    /// the source type given explicitly, leaving the rest to be inferred from the filename
+    fn source_type(&self) -> Option<SourceType> {
+        if self.lang.is_none() && self.source_type.is_none() {
+            return None;
+        }
+        let source_type = match self.lang {
+            // JavaScript files may contain JSX too
+            Some(Lang::Js) => SourceType::mjs().with_jsx(true),
+            Some(Lang::Jsx) => SourceType::jsx(),
+            Some(Lang::Ts) => SourceType::ts(),
+            Some(Lang::Tsx) => SourceType::tsx(),
+            None => self
+                .filename
+                .as_deref()
+                .and_then(|filename| SourceType::from_path(filename).ok())
+                .unwrap_or_else(SourceType::tsx),
+        };
+        Some(match self.source_type {
+            Some(ModuleKind::Module) => source_type.with_module(true),
+            Some(ModuleKind::Script) => source_type.with_script(true),
+            None => source_type,
+        })
+    }
+
+    fn into_core(self) -> Result<core::TransformOptions> {
+        let source_type = self.source_type();
+        let input_source_map = self
+            .input_source_map
+            .map(|map| SourceMap::from_json_string(&map))
+            .transpose()
+            .map_err(|error| Error::new(Status::InvalidArg, error.to_string()))?;
+        Ok(core::TransformOptions {
+            filename: self.filename,
+            source_type,
+            source_maps: self.source_maps.unwrap_or(false),
+            input_source_map,
+        })
+    }
+}
+
+#[napi(object)]
+pub struct TransformOutput {
+    pub code: String,
+    /// the source map of the output as a JSON string
+    pub map: Option<String>,
+}
+
+impl From<core::TransformOutput> for TransformOutput {
+    fn from(output: core::TransformOutput) -> Self {
+        Self {
+            code: output.code,
+            map: output.map.map(|map| map.to_json_string()),
+        }
+    }
+}
+
+/// deserialize the options of the transform, which have the same keys as the ones of the babel
+/// plugin
+fn to_config(config: Option<serde_json::Value>) -> Result<core::Config> {
+    config
+        .map(serde_json::from_value)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|error| Error::new(Status::InvalidArg, error.to_string()))
+}
+
+/// report the diagnostics of a source that couldn't be transformed, which is a failure of the
+/// transform rather than of its arguments
+fn to_napi_error(error: TransformError) -> Error {
+    Error::new(Status::GenericFailure, error.to_string())
+}
 
 #[napi]
-pub fn transform(source: String, config: Option<Config>) -> Result<String> {
-    Ok(oxc_jsx_dom_expressions::transform(source, config.unwrap_or_default().into()).unwrap())
+pub fn transform(
+    source: String,
+    #[napi(ts_arg_type = "Config | undefined | null")] config: Option<serde_json::Value>,
+    options: Option<TransformOptions>,
+) -> Result<String> {
+    let options = options.unwrap_or_default().into_core()?;
+    core::transform_with_options(
+        source,
+        to_config(config)?,
+        core::TransformOptions {
+            source_maps: false,
+            ..options
+        },
+    )
+    .map(|output| output.code)
+    .map_err(to_napi_error)
+}
+
+#[napi]
+pub fn transform_with_options(
+    source: String,
+    #[napi(ts_arg_type = "Config | undefined | null")] config: Option<serde_json::Value>,
+    options: Option<TransformOptions>,
+) -> Result<TransformOutput> {
+    let options = options.unwrap_or_default().into_core()?;
+    core::transform_with_options(source, to_config(config)?, options)
+        .map(TransformOutput::from)
+        .map_err(to_napi_error)
+}
+
+pub struct TransformTask {
+    source: String,
+    config: core::Config,
+    options: core::TransformOptions,
+}
+
+impl Task for TransformTask {
+    type Output = core::TransformOutput;
+    type JsValue = TransformOutput;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        core::transform_with_options(
+            std::mem::take(&mut self.source),
+            std::mem::take(&mut self.config),
+            std::mem::take(&mut self.options),
+        )
+        .map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// transform the source on the thread pool of libuv, without blocking the main thread
+#[napi(ts_return_type = "Promise<TransformOutput>")]
+pub fn transform_async(
+    source: String,
+    #[napi(ts_arg_type = "Config | undefined | null")] config: Option<serde_json::Value>,
+    options: Option<TransformOptions>,
+) -> Result<AsyncTask<TransformTask>> {
+    Ok(AsyncTask::new(TransformTask {
+        source,
+        config: to_config(config)?,
+        options: options.unwrap_or_default().into_core()?,
+    }))
+}
+
+#[napi(object)]
+pub struct TransformFile {
+    pub source: String,
+    pub options: Option<TransformOptions>,
+}
+
+/// the output of a file of the batch, or the error that kept it from being transformed
+#[napi(object)]
+pub struct TransformFileResult {
+    pub output: Option<TransformOutput>,
+    pub error: Option<String>,
+}
+
+pub struct TransformBatchTask {
+    files: Vec<(String, core::TransformOptions)>,
+    config: core::Config,
+}
+
+impl Task for TransformBatchTask {
+    type Output = Vec<TransformFileResult>;
+    type JsValue = Vec<TransformFileResult>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let files = std::mem::take(&mut self.files);
+        let config = &self.config;
+        Ok(files
+            .into_par_iter()
+            .map(|(source, options)| {
+                match core::transform_with_options(source, config.clone(), options) {
+                    Ok(output) => TransformFileResult {
+                        output: Some(output.into()),
+                        error: None,
+                    },
+                    Err(error) => TransformFileResult {
+                        output: None,
+                        error: Some(error.to_string()),
+                    },
+                }
+            })
+            .collect())
+    }
+
+    fn resolve(&mut self, _: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+/// transform the files in parallel, resolving with the result of each of them in their order
+#[napi(ts_return_type = "Promise<Array<TransformFileResult>>")]
+pub fn transform_batch(
+    files: Vec<TransformFile>,
+    #[napi(ts_arg_type = "Config | undefined | null")] config: Option<serde_json::Value>,
+) -> Result<AsyncTask<TransformBatchTask>> {
+    let files = files
+        .into_iter()
+        .map(|file| Ok((file.source, file.options.unwrap_or_default().into_core()?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(AsyncTask::new(TransformBatchTask {
+        files,
+        config: to_config(config)?,
+    }))
 }
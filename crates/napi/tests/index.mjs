@@ -3,7 +3,12 @@
 import test from "node:test";
 import assert from "node:assert";
 
-import { transform } from "../index.js";
+import {
+	transform,
+	transformAsync,
+	transformBatch,
+	transformWithOptions,
+} from "../index.js";
 
 test("transform() returns compiled code", (t) => {
 	assert.strictEqual(
@@ -11,3 +16,49 @@ test("transform() returns compiled code", (t) => {
 		"string",
 	);
 });
+
+test("transform() accepts the generate option", (t) => {
+	const source = "const Comp = () => <div>Hello, world!</div>;";
+	assert.notStrictEqual(transform(source, { generate: "ssr" }), transform(source));
+});
+
+test("transform() rejects unknown options", (t) => {
+	// @ts-expect-error
+	assert.throws(() => transform("const Comp = () => <div />;", { moduleNmae: "r-dom" }), {
+		code: "InvalidArg",
+	});
+});
+
+test("transform() fails on invalid sources", (t) => {
+	assert.throws(() => transform("const Comp = () => <div>;"), { code: "GenericFailure" });
+});
+
+test("transformWithOptions() returns a source map", (t) => {
+	const output = transformWithOptions("const Comp = () => <div>Hello, world!</div>;", null, {
+		filename: "comp.tsx",
+		sourceMaps: true,
+	});
+	assert.strictEqual(typeof output.code, "string");
+	assert.deepStrictEqual(JSON.parse(output.map ?? "").sources, ["comp.tsx"]);
+});
+
+test("transformWithOptions() parses JSX in JavaScript", (t) => {
+	const output = transformWithOptions("const Comp = () => <div>Hello, world!</div>;", null, {
+		lang: "js",
+	});
+	assert.strictEqual(typeof output.code, "string");
+});
+
+test("transformAsync() resolves with the output", async (t) => {
+	const output = await transformAsync("const Comp = () => <div>Hello, world!</div>;");
+	assert.strictEqual(typeof output.code, "string");
+});
+
+test("transformBatch() reports the result of each file", async (t) => {
+	const results = await transformBatch([
+		{ source: "const Comp = () => <div>Hello, world!</div>;" },
+		{ source: "const Comp = () => <div>;" },
+	]);
+	assert.strictEqual(typeof results[0].output?.code, "string");
+	assert.strictEqual(typeof results[1].error, "string");
+});